@@ -0,0 +1,397 @@
+use std::{
+    io::{self, IoSlice},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::Progress;
+
+// ======================================================================
+// ProgressAsyncReader - PUBLIC
+
+pin_project! {
+    /// Async reader which increments position of [`Progress`]
+    /// by the number of bytes read.
+    ///
+    /// This is the async version of [`ProgressReader`],
+    /// available with `async` feature.
+    ///
+    /// Polling never blocks on [`State`] of `Progress`,
+    /// since [`Progress::inc`] doesn't lock it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use tokio::io::AsyncReadExt;
+    /// use ml_progress::{progress, ProgressAsyncReader};
+    ///
+    /// let data = vec![0; 10000];
+    /// let progress = progress!(data.len())?;
+    /// let mut reader = ProgressAsyncReader::new(&data[..], progress);
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf).await?;
+    /// reader.as_progress().finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ProgressReader`]: crate::ProgressReader
+    /// [`State`]: crate::State
+    pub struct ProgressAsyncReader<R> {
+        #[pin]
+        inner: R,
+        progress: Progress,
+    }
+}
+
+impl<R> ProgressAsyncReader<R> {
+    /// Returns [`Progress`] of this reader.
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Returns reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader doesn't update [`Progress`].
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns pinned mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader doesn't update [`Progress`].
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Creates `ProgressAsyncReader` which reads from given reader
+    /// and updates given [`Progress`].
+    ///
+    /// See [`ProgressAsyncReader`] for an example.
+    pub fn new(inner: R, progress: Progress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+// ======================================================================
+// ProgressAsyncReader - IMPL ASYNC READ
+
+impl<R: AsyncRead> AsyncRead for ProgressAsyncReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        let filled = buf.filled().len();
+        let result = this.inner.poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            this.progress.inc((buf.filled().len() - filled) as u64);
+        }
+        result
+    }
+}
+
+// ======================================================================
+// ProgressAsyncWriter - PUBLIC
+
+pin_project! {
+    /// Async writer which increments position of [`Progress`]
+    /// by the number of bytes written.
+    ///
+    /// This is the async version of [`ProgressWriter`],
+    /// available with `async` feature.
+    ///
+    /// Polling never blocks on [`State`] of `Progress`,
+    /// see [`ProgressAsyncReader`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use tokio::io::AsyncWriteExt;
+    /// use ml_progress::{progress, ProgressAsyncWriter};
+    ///
+    /// let data = vec![0; 10000];
+    /// let progress = progress!(data.len())?;
+    /// let mut writer = ProgressAsyncWriter::new(Vec::new(), progress);
+    /// writer.write_all(&data).await?;
+    /// writer.as_progress().finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ProgressWriter`]: crate::ProgressWriter
+    /// [`State`]: crate::State
+    pub struct ProgressAsyncWriter<W> {
+        #[pin]
+        inner: W,
+        progress: Progress,
+    }
+}
+
+impl<W> ProgressAsyncWriter<W> {
+    /// Returns [`Progress`] of this writer.
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Returns reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer doesn't update [`Progress`].
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns pinned mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer doesn't update [`Progress`].
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.project().inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Creates `ProgressAsyncWriter` which writes to given writer
+    /// and updates given [`Progress`].
+    ///
+    /// See [`ProgressAsyncWriter`] for an example.
+    pub fn new(inner: W, progress: Progress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+// ======================================================================
+// ProgressAsyncWriter - IMPL ASYNC WRITE
+
+impl<W: AsyncWrite> AsyncWrite for ProgressAsyncWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let result = this.inner.poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = result {
+            this.progress.inc(n as u64);
+        }
+        result
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let result = this.inner.poll_write_vectored(cx, bufs);
+        if let Poll::Ready(Ok(n)) = result {
+            this.progress.inc(n as u64);
+        }
+        result
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+// ======================================================================
+// ProgressStream - PUBLIC
+
+pin_project! {
+    /// Stream which increments position of [`Progress`]
+    /// by one for each item.
+    ///
+    /// This is the async version of [`ProgressIter`],
+    /// available with `async` feature.
+    /// Unlike `ProgressIter`, `Progress` isn't finished automatically.
+    ///
+    /// Polling never blocks on [`State`] of `Progress`,
+    /// see [`ProgressAsyncReader`] for details.
+    ///
+    /// [`ProgressIter`]: crate::ProgressIter
+    /// [`State`]: crate::State
+    pub struct ProgressStream<S> {
+        #[pin]
+        inner: S,
+        progress: Progress,
+    }
+}
+
+impl<S> ProgressStream<S> {
+    /// Returns [`Progress`] of this stream.
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Returns reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns mutable reference to the underlying stream.
+    ///
+    /// Polling the underlying stream directly doesn't update [`Progress`].
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Returns pinned mutable reference to the underlying stream.
+    ///
+    /// Polling the underlying stream directly doesn't update [`Progress`].
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut S> {
+        self.project().inner
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Creates `ProgressStream` which yields items of given stream
+    /// and updates given [`Progress`].
+    pub fn new(inner: S, progress: Progress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+// ======================================================================
+// ProgressStream - IMPL STREAM
+
+impl<S: Stream> Stream for ProgressStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let result = this.inner.poll_next(cx);
+        if let Poll::Ready(Some(_)) = result {
+            this.progress.inc(1);
+        }
+        result
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        io::Cursor,
+        sync::Arc,
+        task::{Wake, Waker},
+    };
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+    use crate::tests::{pos, sink_progress};
+
+    // `Waker::noop` would need newer Rust than `rust-version`.
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn noop_waker() -> Waker {
+        Waker::from(Arc::new(NoopWaker))
+    }
+
+    // Polls future which is expected to be ready without waiting.
+    fn ready<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(&noop_waker()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future is pending"),
+        }
+    }
+
+    #[test]
+    fn reader() {
+        let mut reader = ProgressAsyncReader::new(Cursor::new(vec![0; 10]), sink_progress());
+        let mut buf = [0; 4];
+        ready(reader.read_exact(&mut buf)).unwrap();
+        assert_eq!(pos(reader.as_progress()), 4);
+    }
+
+    #[test]
+    fn reader_while_locked() {
+        let mut reader = ProgressAsyncReader::new(Cursor::new(vec![0; 10]), sink_progress());
+        let progress = reader.as_progress().clone();
+        let mut buf = [0; 4];
+
+        let state = progress.state().lock();
+        ready(reader.read_exact(&mut buf)).unwrap();
+        drop(state);
+        assert_eq!(pos(&progress), 4);
+    }
+
+    #[test]
+    fn writer() {
+        let mut writer = ProgressAsyncWriter::new(Vec::new(), sink_progress());
+        ready(writer.write_all(b"foobar")).unwrap();
+        assert_eq!(pos(writer.as_progress()), 6);
+        assert_eq!(writer.into_inner(), b"foobar");
+    }
+
+    #[test]
+    fn stream() {
+        struct Count(u32);
+
+        impl Stream for Count {
+            type Item = u32;
+
+            fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+                self.0 += 1;
+                Poll::Ready(if self.0 <= 3 { Some(self.0) } else { None })
+            }
+        }
+
+        let mut stream = ProgressStream::new(Count(0), sink_progress());
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        while let Poll::Ready(Some(_)) = Pin::new(&mut stream).poll_next(&mut cx) {}
+        assert_eq!(pos(stream.as_progress()), 3);
+    }
+}
@@ -0,0 +1,388 @@
+use std::{env, ffi::OsString};
+
+use crate::{text, Style};
+
+// ======================================================================
+// CONST - PRIVATE
+
+// Bouncing block of bar with unknown total is this fraction of bar width.
+const BOUNCING_BLOCK_RATIO: usize = 5;
+
+// Blocks from one eighth to seven eighths of a cell.
+const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+// ======================================================================
+// BarStyle - PUBLIC
+
+/// Characters used to draw a bar.
+///
+/// Style is set for all bars with [`ProgressBuilder::bar_style`]
+/// or for one bar with `(bar_fill STYLE)` or `(bar WIDTH STYLE)` item.
+/// Default is [`BarStyle::ascii`].
+///
+/// - Done part of bar repeats `filled` pattern,
+///   followed by `head` unless bar is full.
+/// - Remaining part of bar repeats `empty` pattern.
+/// - Bar is surrounded by `caps`, if there is room for them.
+///
+/// Patterns are aligned to start of bar, so they don't move as bar grows.
+/// Each character of patterns is expected to be one column wide.
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::{progress_builder, BarStyle};
+///
+/// let progress = progress_builder!()
+///     .total(Some(10))
+///     .bar_style(BarStyle::unicode())
+///     .build()?;
+/// progress.inc(6);
+/// progress.finish_at_current_pos();
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// ```text
+/// ██████████████████████████████                     6/10 (2s)
+/// ```
+///
+/// ```rust
+/// use ml_progress::{progress_builder, BarStyle};
+///
+/// const ARROW: BarStyle = BarStyle::ascii()
+///     .filled("=")
+///     .empty(" ")
+///     .head(">")
+///     .caps("[", "]");
+///
+/// let progress = progress_builder!((bar_fill ARROW) " " pos "/" total)
+///     .total(Some(10))
+///     .build()?;
+/// progress.inc(6);
+/// progress.finish_at_current_pos();
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// ```text
+/// [============================>                   ] 6/10
+/// ```
+///
+/// [`ProgressBuilder::bar_style`]: crate::ProgressBuilder::bar_style
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BarStyle {
+    filled: &'static str,
+    empty: &'static str,
+    head: Option<&'static str>,
+    caps: (&'static str, &'static str),
+    is_unicode: bool,
+}
+
+impl BarStyle {
+    /// Creates style with `#` for done and `-` for remaining part.
+    pub const fn ascii() -> Self {
+        Self {
+            filled: "#",
+            empty: "-",
+            head: None,
+            caps: ("", ""),
+            is_unicode: false,
+        }
+    }
+
+    /// Returns this style with given left and right caps.
+    pub const fn caps(self, left: &'static str, right: &'static str) -> Self {
+        Self {
+            caps: (left, right),
+            ..self
+        }
+    }
+
+    /// Returns this style with given pattern for remaining part.
+    ///
+    /// Empty pattern is shown as spaces.
+    pub const fn empty(self, pattern: &'static str) -> Self {
+        Self {
+            empty: pattern,
+            ..self
+        }
+    }
+
+    /// Returns this style with given pattern for done part.
+    ///
+    /// Empty pattern is shown as spaces.
+    pub const fn filled(self, pattern: &'static str) -> Self {
+        Self {
+            filled: pattern,
+            ..self
+        }
+    }
+
+    /// Returns this style with given head shown after done part
+    /// unless bar is full.
+    ///
+    /// With [`BarStyle::unicode`] this is shown instead of partial blocks.
+    pub const fn head(self, head: &'static str) -> Self {
+        Self {
+            head: Some(head),
+            ..self
+        }
+    }
+
+    /// Creates style with Unicode block elements,
+    /// giving eight times finer resolution than [`BarStyle::ascii`].
+    ///
+    /// If locale doesn't use UTF-8, as determined by
+    /// `LC_ALL`, `LC_CTYPE` and `LANG` environment variables,
+    /// then `filled` and `empty` patterns of [`BarStyle::ascii`]
+    /// are used instead, without partial blocks.
+    pub const fn unicode() -> Self {
+        Self {
+            filled: "█",
+            empty: " ",
+            head: None,
+            caps: ("", ""),
+            is_unicode: true,
+        }
+    }
+}
+
+// ======================================================================
+// BarStyle - CRATE
+
+impl BarStyle {
+    // Returns bar of given width, with given styles
+    // of done and remaining part applied if any.
+    pub(crate) fn draw(
+        &self,
+        width: usize,
+        fraction: f64,
+        colors: Option<&(Style, Style)>,
+    ) -> String {
+        let (caps, width) = self.fit_caps(width);
+
+        let units = if self.is_unicode && self.head.is_none() {
+            8
+        } else {
+            1
+        };
+        let done_units = (((width * units) as f64 * fraction) as usize).min(width * units);
+        let (full, part) = (done_units / units, done_units % units);
+
+        let mut done = pattern(self.filled, 0, full);
+        if full < width {
+            if let Some(head) = self.head {
+                done.push_str(&text::truncate(head, width - full));
+            } else if part > 0 {
+                done.push(PARTIAL_BLOCKS[part - 1]);
+            }
+        }
+        let done_width = text::width(&done);
+        let mut remaining = pattern(self.empty, done_width, width - done_width);
+
+        if let Some((done_style, remaining_style)) = colors {
+            done = done_style.paint(&done);
+            remaining = remaining_style.paint(&remaining);
+        }
+        format!("{}{}{}{}", caps.0, done, remaining, caps.1)
+    }
+
+    // Returns bar of given width for unknown total, with a block of `filled`
+    // pattern bouncing between ends of bar one cell per tick.
+    pub(crate) fn draw_bouncing(
+        &self,
+        width: usize,
+        tick: usize,
+        colors: Option<&(Style, Style)>,
+    ) -> String {
+        let (caps, width) = self.fit_caps(width);
+
+        let block_width = (width / BOUNCING_BLOCK_RATIO).max(1).min(width);
+        let range = width - block_width;
+        let block_pos = if range == 0 {
+            0
+        } else {
+            let pos = tick % (2 * range);
+            pos.min(2 * range - pos)
+        };
+
+        let mut before = pattern(self.empty, 0, block_pos);
+        let mut block = pattern(self.filled, block_pos, block_width);
+        let mut after = pattern(
+            self.empty,
+            block_pos + block_width,
+            width - block_pos - block_width,
+        );
+
+        if let Some((done_style, remaining_style)) = colors {
+            before = remaining_style.paint(&before);
+            block = done_style.paint(&block);
+            after = remaining_style.paint(&after);
+        }
+        format!("{}{}{}{}{}", caps.0, before, block, after, caps.1)
+    }
+
+    // Returns this style, or with ASCII patterns if locale doesn't use UTF-8.
+    pub(crate) fn for_locale(self) -> Self {
+        if self.is_unicode && !is_utf8_locale(|name| env::var_os(name)) {
+            let ascii = Self::ascii();
+            Self {
+                filled: ascii.filled,
+                empty: ascii.empty,
+                is_unicode: false,
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+}
+
+// ======================================================================
+// BarStyle - PRIVATE
+
+impl BarStyle {
+    // Returns caps and width of bar between them,
+    // without caps if there is no room for them.
+    fn fit_caps(&self, width: usize) -> ((&'static str, &'static str), usize) {
+        let caps_width = text::width(self.caps.0) + text::width(self.caps.1);
+        if caps_width <= width {
+            (self.caps, width - caps_width)
+        } else {
+            (("", ""), width)
+        }
+    }
+}
+
+// ======================================================================
+// BarStyle - IMPL DEFAULT
+
+impl Default for BarStyle {
+    fn default() -> Self {
+        Self::ascii()
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+// Returns `true` if first set locale variable specifies UTF-8.
+fn is_utf8_locale(var: impl Fn(&str) -> Option<OsString>) -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.is_empty())
+        .is_some_and(|value| {
+            let value = value.to_string_lossy().to_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+}
+
+// Returns `count` characters of repeated pattern, starting from cell `offset` of bar.
+fn pattern(pattern: &str, offset: usize, count: usize) -> String {
+    if pattern.is_empty() {
+        " ".repeat(count)
+    } else {
+        pattern.chars().cycle().skip(offset).take(count).collect()
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(style: BarStyle, width: usize, fraction: f64) -> String {
+        style.draw(width, fraction, None)
+    }
+
+    fn draw_bouncing(style: BarStyle, width: usize, tick: usize) -> String {
+        style.draw_bouncing(width, tick, None)
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(draw(BarStyle::ascii(), 4, 0.6), "##--");
+        assert_eq!(draw(BarStyle::ascii(), 4, 1.5), "####");
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(draw(BarStyle::unicode(), 4, 0.0), "    ");
+        assert_eq!(draw(BarStyle::unicode(), 4, 0.25), "█   ");
+        assert_eq!(draw(BarStyle::unicode(), 4, 0.5625), "██▎ ");
+        assert_eq!(draw(BarStyle::unicode(), 4, 0.99), "███▉");
+        assert_eq!(draw(BarStyle::unicode(), 4, 1.5), "████");
+    }
+
+    #[test]
+    fn head_and_caps() {
+        let style = BarStyle::ascii()
+            .filled("=")
+            .empty(" ")
+            .head(">")
+            .caps("[", "]");
+        assert_eq!(draw(style.clone(), 6, 0.0), "[>   ]");
+        assert_eq!(draw(style.clone(), 6, 0.5), "[==> ]");
+        assert_eq!(draw(style.clone(), 6, 1.0), "[====]");
+        assert_eq!(draw(style, 1, 0.5), ">");
+    }
+
+    #[test]
+    fn patterns() {
+        let style = BarStyle::ascii().filled("=-").empty(".:");
+        assert_eq!(draw(style.clone(), 7, 0.5), "=-=:.:.");
+        assert_eq!(draw(style, 7, 0.6), "=-=-.:.");
+        assert_eq!(draw(BarStyle::ascii().empty(""), 4, 0.5), "##  ");
+    }
+
+    #[test]
+    fn bouncing() {
+        let frames = (0..8)
+            .map(|tick| draw_bouncing(BarStyle::ascii().caps("[", "]"), 12, tick))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            [
+                "[##--------]",
+                "[-##-------]",
+                "[--##------]",
+                "[---##-----]",
+                "[----##----]",
+                "[-----##---]",
+                "[------##--]",
+                "[-------##-]",
+            ]
+        );
+        assert_eq!(draw_bouncing(BarStyle::ascii(), 10, 8), "--------##");
+        assert_eq!(draw_bouncing(BarStyle::ascii(), 10, 9), "-------##-");
+        assert_eq!(draw_bouncing(BarStyle::ascii(), 10, 16), "##--------");
+        assert_eq!(draw_bouncing(BarStyle::ascii(), 1, 3), "#");
+    }
+
+    #[test]
+    fn colors() {
+        let colors = (Style::new().bold(), Style::new());
+        assert_eq!(
+            BarStyle::ascii().caps("[", "]").draw(4, 0.5, Some(&colors)),
+            "[\x1b[1m#\x1b[0m-]"
+        );
+    }
+
+    #[test]
+    fn utf8_locale() {
+        let locale = |vars: &'static [(&str, &str)]| {
+            is_utf8_locale(move |name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| OsString::from(value))
+            })
+        };
+
+        assert!(locale(&[("LANG", "en_US.UTF-8")]));
+        assert!(locale(&[("LC_ALL", ""), ("LC_CTYPE", "fi_FI.utf8")]));
+        assert!(!locale(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")]));
+        assert!(!locale(&[]));
+    }
+}
@@ -0,0 +1,376 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+// ======================================================================
+// CONST - PRIVATE
+
+const MIN_ETA_ELAPSED: Duration = Duration::from_millis(100);
+const MIN_SPEED_ELAPSED: Duration = Duration::from_millis(100);
+
+// ======================================================================
+// Estimator - PUBLIC
+
+/// Estimates speed and time remaining of [`Progress`].
+///
+/// Estimator is set with [`ProgressBuilder::estimator`],
+/// default is [`AverageEstimator`].
+///
+/// Estimator is given a sample whenever position of `Progress` has changed
+/// and [`State`] is updated, i.e. when `Progress` is drawn.
+/// First sample is `0.0` steps at creation of `Progress`.
+///
+/// Built-in estimators are [`AverageEstimator`], [`EmaEstimator`]
+/// and [`WindowEstimator`].
+///
+/// # Examples
+///
+/// Estimator for work where later steps are slower,
+/// e.g. step `n` takes time proportional to `n`.
+///
+/// ```rust
+/// use std::time::{Duration, Instant};
+/// use ml_progress::{progress_builder, Estimator};
+///
+/// #[derive(Default)]
+/// struct Quadratic {
+///     start: Option<Instant>,
+///     latest: Option<(Instant, f64)>,
+/// }
+///
+/// impl Estimator for Quadratic {
+///     fn add(&mut self, time: Instant, pos: f64) {
+///         self.start.get_or_insert(time);
+///         self.latest = Some((time, pos));
+///     }
+///
+///     fn remaining(&self, total: u64) -> Option<Duration> {
+///         let (time, pos) = self.latest?;
+///         let elapsed = time - self.start?;
+///         (pos > 0.0).then(|| elapsed.mul_f64((total as f64 / pos).powi(2) - 1.0))
+///     }
+///
+///     fn speed(&self) -> Option<f64> {
+///         let (time, pos) = self.latest?;
+///         let elapsed = time - self.start?;
+///         (pos > 0.0).then(|| pos / (2.0 * elapsed.as_secs_f64()))
+///     }
+/// }
+///
+/// let progress = progress_builder!()
+///     .total(Some(10))
+///     .estimator(Quadratic::default())
+///     .build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// [`Progress`]: crate::Progress
+/// [`ProgressBuilder::estimator`]: crate::ProgressBuilder::estimator
+/// [`State`]: crate::State
+pub trait Estimator: Send + 'static {
+    /// Adds sample of `pos` steps completed at `time`.
+    ///
+    /// `pos` can be fractional with [children] and it's one less than
+    /// position with [`ProgressBuilder::pre_inc`].
+    ///
+    /// [children]: crate::Progress::child
+    /// [`ProgressBuilder::pre_inc`]: crate::ProgressBuilder::pre_inc
+    fn add(&mut self, time: Instant, pos: f64);
+
+    /// Returns estimated duration from latest sample until
+    /// `total` steps have been completed, or `None` if not available.
+    ///
+    /// This is called only if latest sample is at most `total`.
+    fn remaining(&self, total: u64) -> Option<Duration>;
+
+    /// Returns estimated speed in steps per second at latest sample,
+    /// or `None` if not available.
+    fn speed(&self) -> Option<f64>;
+}
+
+// ======================================================================
+// AverageEstimator - PUBLIC
+
+/// [`Estimator`] using average speed from when [`Progress`] was created.
+///
+/// This is the default estimator.
+///
+/// Speed is available if
+/// - at least one step has been completed and
+/// - at least 100 ms has elapsed since [`Progress`] creation.
+///
+/// Time remaining is available if speed is available and positive.
+///
+/// Speed is never negative, e.g. it's zero after position has decreased.
+///
+/// [`Progress`]: crate::Progress
+#[derive(Clone, Debug, Default)]
+pub struct AverageEstimator {
+    first: Option<(Instant, f64)>,
+    latest: Option<(Instant, f64)>,
+}
+
+impl AverageEstimator {
+    /// Creates `AverageEstimator`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Estimator for AverageEstimator {
+    fn add(&mut self, time: Instant, pos: f64) {
+        self.first.get_or_insert((time, pos));
+        self.latest = Some((time, pos));
+    }
+
+    fn remaining(&self, total: u64) -> Option<Duration> {
+        remaining(self.first?, self.latest?, self.speed()?, total)
+    }
+
+    fn speed(&self) -> Option<f64> {
+        speed(self.first?, self.latest?)
+    }
+}
+
+// ======================================================================
+// EmaEstimator - PUBLIC
+
+/// [`Estimator`] using exponential moving average of speed.
+///
+/// Speed from given half-life ago has half the weight of current speed.
+/// Speed and time remaining are available like with [`AverageEstimator`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use ml_progress::{progress_builder, EmaEstimator};
+///
+/// let progress = progress_builder!()
+///     .estimator(EmaEstimator::new(Duration::from_secs(10)))
+///     .build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct EmaEstimator {
+    half_life: Duration,
+    first: Option<(Instant, f64)>,
+    latest: Option<(Instant, f64)>,
+    speed: Option<f64>,
+}
+
+impl EmaEstimator {
+    /// Creates `EmaEstimator` with given half-life.
+    pub fn new(half_life: Duration) -> Self {
+        Self {
+            half_life,
+            first: None,
+            latest: None,
+            speed: None,
+        }
+    }
+}
+
+impl Estimator for EmaEstimator {
+    fn add(&mut self, time: Instant, pos: f64) {
+        if let Some((prev_time, prev_pos)) = self.latest {
+            if time <= prev_time {
+                return;
+            }
+            let elapsed = time - prev_time;
+            let speed = (pos - prev_pos) / elapsed.as_secs_f64();
+            self.speed = Some(match self.speed {
+                Some(prev_speed) => {
+                    let alpha = 1.0 - 0.5f64.powf(elapsed.div_duration_f64(self.half_life));
+                    prev_speed + alpha * (speed - prev_speed)
+                }
+                None => speed,
+            });
+        }
+        self.first.get_or_insert((time, pos));
+        self.latest = Some((time, pos));
+    }
+
+    fn remaining(&self, total: u64) -> Option<Duration> {
+        remaining(self.first?, self.latest?, self.speed()?, total)
+    }
+
+    fn speed(&self) -> Option<f64> {
+        // Availability is same as with `AverageEstimator`.
+        speed(self.first?, self.latest?).and(self.speed.map(|speed| speed.max(0.0)))
+    }
+}
+
+// ======================================================================
+// WindowEstimator - PUBLIC
+
+/// [`Estimator`] using average speed during given duration,
+/// e.g. during last 10 seconds.
+///
+/// Speed and time remaining are available like with [`AverageEstimator`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use ml_progress::{progress_builder, WindowEstimator};
+///
+/// let progress = progress_builder!()
+///     .estimator(WindowEstimator::new(Duration::from_secs(10)))
+///     .build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct WindowEstimator {
+    window: Duration,
+    first: Option<(Instant, f64)>,
+    // Samples within the window and one sample at or before start of the window.
+    samples: VecDeque<(Instant, f64)>,
+}
+
+impl WindowEstimator {
+    /// Creates `WindowEstimator` with given window duration.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            first: None,
+            samples: VecDeque::new(),
+        }
+    }
+}
+
+impl Estimator for WindowEstimator {
+    fn add(&mut self, time: Instant, pos: f64) {
+        if self.samples.back().is_some_and(|&(prev, _)| time <= prev) {
+            return;
+        }
+        while self.samples.len() > 1 && self.samples[1].0 + self.window <= time {
+            self.samples.pop_front();
+        }
+        self.first.get_or_insert((time, pos));
+        self.samples.push_back((time, pos));
+    }
+
+    fn remaining(&self, total: u64) -> Option<Duration> {
+        remaining(self.first?, *self.samples.back()?, self.speed()?, total)
+    }
+
+    fn speed(&self) -> Option<f64> {
+        // Availability is same as with `AverageEstimator`.
+        speed(self.first?, *self.samples.back()?)?;
+        speed(*self.samples.front()?, *self.samples.back()?)
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+// Returns remaining duration for built-in estimators.
+fn remaining(
+    (first_time, _): (Instant, f64),
+    (time, pos): (Instant, f64),
+    speed: f64,
+    total: u64,
+) -> Option<Duration> {
+    if time - first_time >= MIN_ETA_ELAPSED && speed > 0.0 {
+        Duration::try_from_secs_f64((total as f64 - pos) / speed).ok()
+    } else {
+        None
+    }
+}
+
+// Returns average speed between given samples for built-in estimators,
+// zero if position has decreased.
+fn speed((first_time, first_pos): (Instant, f64), (time, pos): (Instant, f64)) -> Option<f64> {
+    if time - first_time >= MIN_SPEED_ELAPSED && pos > 0.0 {
+        Some(((pos - first_pos) / (time - first_time).as_secs_f64()).max(0.0))
+    } else {
+        None
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speeds(mut estimator: impl Estimator, samples: &[(u64, f64)]) -> Vec<Option<f64>> {
+        let start = Instant::now();
+        estimator.add(start, 0.0);
+        samples
+            .iter()
+            .map(|&(secs, pos)| {
+                estimator.add(start + Duration::from_secs(secs), pos);
+                estimator.speed()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn average() {
+        assert_eq!(
+            speeds(
+                AverageEstimator::new(),
+                &[(1, 0.0), (1, 1.0), (2, 10.0), (4, 12.0)]
+            ),
+            [None, Some(1.0), Some(5.0), Some(3.0)]
+        );
+    }
+
+    #[test]
+    fn average_remaining() {
+        let start = Instant::now();
+        let mut estimator = AverageEstimator::new();
+        estimator.add(start, 0.0);
+        assert_eq!(estimator.remaining(10), None);
+        estimator.add(start + Duration::from_secs(2), 4.0);
+        assert_eq!(estimator.remaining(10), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn ema() {
+        assert_eq!(
+            speeds(
+                EmaEstimator::new(Duration::from_secs(1)),
+                &[(1, 4.0), (2, 6.0), (4, 30.0)]
+            ),
+            [Some(4.0), Some(3.0), Some(9.75)]
+        );
+    }
+
+    #[test]
+    fn window() {
+        assert_eq!(
+            speeds(
+                WindowEstimator::new(Duration::from_secs(2)),
+                &[(1, 1.0), (2, 2.0), (3, 7.0), (4, 13.0), (10, 13.0)]
+            ),
+            [Some(1.0), Some(1.0), Some(3.0), Some(5.5), Some(0.0)]
+        );
+    }
+
+    #[test]
+    fn decreasing_pos_has_zero_speed() {
+        let samples = &[(1, 10.0), (2, 2.0)];
+        assert_eq!(
+            speeds(EmaEstimator::new(Duration::from_millis(100)), samples),
+            [Some(10.0), Some(0.0)]
+        );
+        assert_eq!(
+            speeds(WindowEstimator::new(Duration::from_secs(1)), samples),
+            [Some(10.0), Some(0.0)]
+        );
+    }
+
+    #[test]
+    fn remaining_too_long_is_none() {
+        let start = Instant::now();
+        let mut estimator = AverageEstimator::new();
+        estimator.add(start, 0.0);
+        estimator.add(start + Duration::from_secs(1), 1e-9);
+        assert_eq!(estimator.remaining(u64::MAX), None);
+    }
+}
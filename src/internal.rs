@@ -3,18 +3,87 @@
 //! These are not meant to be used directly
 //! but need to be public for macros to work.
 
-use std::fmt;
+use std::{
+    cmp::Reverse,
+    fmt,
+    ops::{Bound, RangeBounds},
+};
 
-use crate::State;
+use crate::{BarStyle, State, Style, Truncate};
 
 // ======================================================================
 // Item - PUBLIC
 
 /// _Internal_ An item shown on progress indicator line.
 pub enum Item {
+    Bar(usize, Option<BarStyle>),
     Fill(FillItem),
     Fn(Box<dyn Fn(&State) -> String + Send + Sync>),
     Literal(String),
+    Priority(u32, Vec<Item>),
+    Spinner(&'static [&'static str]),
+    Styled(Style, Box<Item>),
+    Weighted(FillWidth, Box<Item>),
+}
+
+// ======================================================================
+// Item - CRATE
+
+impl Item {
+    // Returns this item with any bar style adjusted for locale.
+    pub(crate) fn for_locale(self) -> Self {
+        match self {
+            Item::Bar(width, Some(style)) => Item::Bar(width, Some(style.for_locale())),
+            Item::Fill(FillItem::Bar(Some(style))) => {
+                Item::Fill(FillItem::Bar(Some(style.for_locale())))
+            }
+            Item::Priority(priority, items) => {
+                Item::Priority(priority, items.into_iter().map(Item::for_locale).collect())
+            }
+            Item::Styled(style, item) => Item::Styled(style, Box::new(item.for_locale())),
+            Item::Weighted(width, item) => Item::Weighted(width, Box::new(item.for_locale())),
+            item => item,
+        }
+    }
+
+    // Returns `true` if this item changes over time without changes in state,
+    // i.e. it's a spinner or a bar with unknown total.
+    pub(crate) fn is_animated(&self, is_total_known: bool) -> bool {
+        match self {
+            Item::Bar(..) | Item::Fill(FillItem::Bar(_)) => !is_total_known,
+            Item::Priority(_, items) => items.iter().any(|item| item.is_animated(is_total_known)),
+            Item::Styled(_, item) | Item::Weighted(_, item) => item.is_animated(is_total_known),
+            Item::Spinner(_) => true,
+            _ => false,
+        }
+    }
+
+    // Returns width constraints of this fill item.
+    pub(crate) fn fill_width(&self) -> FillWidth {
+        match self {
+            Item::Styled(_, item) => item.fill_width(),
+            Item::Weighted(width, _) => *width,
+            _ => FillWidth::new(1, ..),
+        }
+    }
+
+    // Returns `true` if this is a fill item, possibly styled or weighted.
+    pub(crate) fn is_fill(&self) -> bool {
+        match self {
+            Item::Fill(_) => true,
+            Item::Styled(_, item) | Item::Weighted(_, item) => item.is_fill(),
+            _ => false,
+        }
+    }
+
+    // Returns priority of this item, or `None` if this item is never dropped.
+    pub(crate) fn priority(&self) -> Option<u32> {
+        match self {
+            Item::Priority(priority, _) => Some(*priority),
+            Item::Styled(_, item) | Item::Weighted(_, item) => item.priority(),
+            _ => None,
+        }
+    }
 }
 
 // ======================================================================
@@ -22,8 +91,49 @@ pub enum Item {
 
 /// _Internal_ An item which fills remaining space on the line.
 pub enum FillItem {
-    Bar,
-    Message,
+    Bar(Option<BarStyle>),
+    Message(Truncate),
+}
+
+// ======================================================================
+// FillWidth - PUBLIC
+
+/// _Internal_ Weight and allowed width range of a fill item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillWidth {
+    weight: u32,
+    min: usize,
+    max: usize,
+}
+
+impl FillWidth {
+    pub fn new(weight: u32, range: impl RangeBounds<usize>) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(&min) => min,
+            Bound::Excluded(&min) => min.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let max = match range.end_bound() {
+            Bound::Included(&max) => max,
+            Bound::Excluded(&max) => max.saturating_sub(1),
+            Bound::Unbounded => usize::MAX,
+        };
+
+        Self {
+            weight,
+            min,
+            max: max.max(min),
+        }
+    }
+}
+
+// ======================================================================
+// FillWidth - CRATE
+
+impl FillWidth {
+    pub(crate) fn min(&self) -> usize {
+        self.min
+    }
 }
 
 // ======================================================================
@@ -137,6 +247,61 @@ impl fmt::Display for FormatPrefix {
     }
 }
 
+// ======================================================================
+// FUNCTIONS - CRATE
+
+// Splits given width between fill items with given constraints.
+//
+// First each item gets its minimum width, in order while width lasts.
+// Rest is split in proportion to weights without exceeding maximum widths,
+// with cells left over from rounding given to largest remainders,
+// leftmost first if equal.
+pub(crate) fn split_fill_width(fills: &[FillWidth], width: usize) -> Vec<usize> {
+    let mut widths = Vec::with_capacity(fills.len());
+    let mut remaining = width;
+    for fill in fills {
+        let min = fill.min.min(remaining);
+        widths.push(min);
+        remaining -= min;
+    }
+
+    while remaining > 0 {
+        let open = (0..fills.len())
+            .filter(|&index| fills[index].weight > 0 && widths[index] < fills[index].max)
+            .collect::<Vec<_>>();
+        if open.is_empty() {
+            break;
+        }
+
+        let total_weight: u128 = open.iter().map(|&index| fills[index].weight as u128).sum();
+        let mut shares = open
+            .iter()
+            .map(|&index| {
+                let amount = remaining as u128 * fills[index].weight as u128;
+                (
+                    index,
+                    (amount / total_weight) as usize,
+                    amount % total_weight,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let leftover = remaining - shares.iter().map(|(_, share, _)| share).sum::<usize>();
+        shares.sort_by_key(|&(index, _, remainder)| (Reverse(remainder), index));
+        for (share, _) in shares.iter_mut().zip(0..leftover) {
+            share.1 += 1;
+        }
+
+        for (index, share, _) in shares {
+            let share = share.min(fills[index].max - widths[index]);
+            widths[index] += share;
+            remaining -= share;
+        }
+    }
+
+    widths
+}
+
 // ======================================================================
 // TESTS
 
@@ -144,6 +309,29 @@ impl fmt::Display for FormatPrefix {
 mod tests {
     use super::*;
 
+    // ============================================================
+    // split_fill_width
+
+    #[test]
+    fn split_fill_width_by_weight() {
+        let fills = [FillWidth::new(2, ..), FillWidth::new(1, ..)];
+        assert_eq!(split_fill_width(&fills, 30), [20, 10]);
+        assert_eq!(split_fill_width(&fills, 31), [21, 10]);
+        assert_eq!(split_fill_width(&fills, 2), [1, 1]);
+        assert_eq!(split_fill_width(&fills, 0), [0, 0]);
+    }
+
+    #[test]
+    fn split_fill_width_with_min_and_max() {
+        let fills = [FillWidth::new(1, ..=5), FillWidth::new(1, 8..)];
+        assert_eq!(split_fill_width(&fills, 30), [5, 25]);
+        assert_eq!(split_fill_width(&fills, 10), [1, 9]);
+        assert_eq!(split_fill_width(&fills, 6), [0, 6]);
+
+        let fills = [FillWidth::new(1, ..4), FillWidth::new(0, 2..)];
+        assert_eq!(split_fill_width(&fills, 10), [3, 2]);
+    }
+
     // ============================================================
     // FormatFloat
 
@@ -0,0 +1,316 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use crate::{Progress, ProgressBuilder};
+
+// ======================================================================
+// ProgressReader - PUBLIC
+
+/// Reader which increments position of [`Progress`] by the number of bytes read.
+///
+/// - [`BufRead`] is passed through, with position incremented
+///   by the number of bytes consumed.
+/// - [`Seek`] is passed through, with position set to the new stream position.
+///   This isn't counted as bytes read for speed and ETA.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Read;
+/// use ml_progress::{progress_builder, ProgressReader};
+///
+/// let data = vec![0; 10000];
+/// let progress = progress_builder!(pos_bin "/" total_bin " " bar_fill " " speed_bin "B/s")
+///     .total(Some(data.len()))
+///     .build()?;
+/// let mut reader = ProgressReader::new(&data[..], progress);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// reader.as_progress().finish();
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ProgressReader<R> {
+    inner: R,
+    progress: Progress,
+}
+
+impl<R> ProgressReader<R> {
+    /// Returns [`Progress`] of this reader.
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Returns reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader doesn't update [`Progress`].
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Creates `ProgressReader` which reads from given reader
+    /// and updates given [`Progress`].
+    ///
+    /// See [`ProgressReader`] for an example.
+    pub fn new(inner: R, progress: Progress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+impl ProgressReader<File> {
+    /// Creates `ProgressReader` which reads from given file, with [`Progress`]
+    /// created from given [`ProgressBuilder`].
+    ///
+    /// - Total is file size from file metadata,
+    ///   unless total of given `ProgressBuilder` is `Some`.
+    /// - Position is current stream position of the file.
+    ///   This isn't counted as bytes read for speed and ETA.
+    ///
+    /// Errors from [`ProgressBuilder::build`] are returned as
+    /// [`io::ErrorKind::InvalidInput`].
+    pub fn from_file(mut file: File, builder: ProgressBuilder) -> io::Result<Self> {
+        let len = file.metadata()?.len();
+        let pos = file.stream_position()?;
+        let progress = builder
+            .default_total(Some(len))
+            .build()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        progress.set_pos(pos);
+        Ok(Self::new(file, progress))
+    }
+
+    /// Opens file at given path in read-only mode and creates `ProgressReader`
+    /// which reads from it, with [`Progress`] created from given [`ProgressBuilder`].
+    ///
+    /// See [`from_file`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{fs::File, io};
+    /// use ml_progress::{progress_builder, ProgressReader};
+    ///
+    /// let builder = progress_builder!(pos_bin "/" total_bin " " bar_fill " " speed_bin "B/s");
+    /// let mut reader = ProgressReader::open("input.bin", builder)?;
+    /// io::copy(&mut reader, &mut File::create("output.bin")?)?;
+    /// reader.as_progress().finish();
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    ///
+    /// [`from_file`]: ProgressReader::from_file
+    pub fn open(path: impl AsRef<Path>, builder: ProgressBuilder) -> io::Result<Self> {
+        Self::from_file(File::open(path)?, builder)
+    }
+}
+
+// ======================================================================
+// ProgressReader - IMPL READ
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.progress.inc(n as u64);
+        Ok(n)
+    }
+}
+
+// ======================================================================
+// ProgressReader - IMPL BUF READ
+
+impl<R: BufRead> BufRead for ProgressReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.progress.inc(amt as u64);
+    }
+}
+
+// ======================================================================
+// ProgressReader - IMPL SEEK
+
+impl<R: Seek> Seek for ProgressReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = self.inner.seek(pos)?;
+        self.progress.set_pos(pos);
+        Ok(pos)
+    }
+}
+
+// ======================================================================
+// ProgressWriter - PUBLIC
+
+/// Writer which increments position of [`Progress`] by the number of bytes written.
+///
+/// - [`Seek`] is passed through, with position set to the new stream position.
+///   This isn't counted as bytes written for speed and ETA.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::File, io};
+/// use ml_progress::{progress_builder, ProgressWriter};
+///
+/// let mut input = File::open("input.bin")?;
+/// let progress = progress_builder!(pos_bin "/" total_bin " " bar_fill " " speed_bin "B/s")
+///     .total(Some(input.metadata()?.len()))
+///     .build()?;
+/// let mut writer = ProgressWriter::new(File::create("output.bin")?, progress);
+/// io::copy(&mut input, &mut writer)?;
+/// writer.as_progress().finish();
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ProgressWriter<W> {
+    inner: W,
+    progress: Progress,
+}
+
+impl<W> ProgressWriter<W> {
+    /// Returns [`Progress`] of this writer.
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Returns reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer doesn't update [`Progress`].
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Creates `ProgressWriter` which writes to given writer
+    /// and updates given [`Progress`].
+    ///
+    /// See [`ProgressWriter`] for an example.
+    pub fn new(inner: W, progress: Progress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+// ======================================================================
+// ProgressWriter - IMPL WRITE
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.progress.inc(n as u64);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// ======================================================================
+// ProgressWriter - IMPL SEEK
+
+impl<W: Seek> Seek for ProgressWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = self.inner.seek(pos)?;
+        self.progress.set_pos(pos);
+        Ok(pos)
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, thread, time::Duration};
+
+    use super::*;
+    use crate::tests::{pos, sink_builder, sink_progress};
+
+    #[test]
+    fn reader_read_and_seek() {
+        let mut reader = ProgressReader::new(Cursor::new(vec![0; 10]), sink_progress());
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(pos(reader.as_progress()), 4);
+        reader.seek(SeekFrom::Current(-3)).unwrap();
+        assert_eq!(pos(reader.as_progress()), 1);
+    }
+
+    #[test]
+    fn reader_from_file() {
+        let path = std::env::temp_dir().join("ml-progress-reader-from-file.txt");
+        std::fs::write(&path, b"foobar").unwrap();
+        let mut file = File::open(&path).unwrap();
+        file.seek(SeekFrom::Start(2)).unwrap();
+        let reader = ProgressReader::from_file(file, sink_builder()).unwrap();
+        assert_eq!(reader.as_progress().state().lock().total(), Some(6));
+        assert_eq!(pos(reader.as_progress()), 2);
+        drop(reader);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reader_from_file_offset_excluded_from_speed() {
+        let path = std::env::temp_dir().join("ml-progress-reader-from-file-offset.txt");
+        std::fs::write(&path, [0; 1000]).unwrap();
+        let mut file = File::open(&path).unwrap();
+        file.seek(SeekFrom::Start(900)).unwrap();
+        let mut reader = ProgressReader::from_file(file, sink_builder()).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        reader.read_exact(&mut [0; 1]).unwrap();
+        let state = reader.as_progress().state().lock();
+        assert!(state.speed().unwrap() < 20.0);
+        assert!(state.eta().unwrap() > Duration::from_secs(5));
+        drop(state);
+        drop(reader);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reader_seek_excluded_from_speed() {
+        let mut reader = ProgressReader::new(Cursor::new(vec![0; 1000]), sink_progress());
+        reader.seek(SeekFrom::Start(900)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        reader.read_exact(&mut [0; 1]).unwrap();
+        assert!(reader.as_progress().state().lock().speed().unwrap() < 20.0);
+    }
+
+    #[test]
+    fn reader_buf_read() {
+        let mut reader = ProgressReader::new(&b"foo\nbar\n"[..], sink_progress());
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "foo\n");
+        assert_eq!(pos(reader.as_progress()), 4);
+    }
+
+    #[test]
+    fn writer_write_and_seek() {
+        let mut writer = ProgressWriter::new(Cursor::new(Vec::new()), sink_progress());
+        writer.write_all(b"foobar").unwrap();
+        assert_eq!(pos(writer.as_progress()), 6);
+        writer.seek(SeekFrom::Start(2)).unwrap();
+        assert_eq!(pos(writer.as_progress()), 2);
+    }
+}
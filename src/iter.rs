@@ -0,0 +1,173 @@
+use crate::{Error, Progress, ProgressBuilder};
+
+// ======================================================================
+// ProgressIterator - PUBLIC
+
+/// Extension trait to wrap any [`Iterator`] with [`Progress`].
+///
+/// - Total is taken from [`Iterator::size_hint`] if it's exact,
+///   which is always the case with [`ExactSizeIterator`].
+/// - [`Progress::inc`] is called with `1` whenever iterator returns an item.
+/// - [`Progress::finish`] is called when iterator is exhausted.
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::ProgressIterator;
+///
+/// for _ in (0..10).progress() {
+///     // std::thread::sleep(std::time::Duration::from_millis(500));
+/// }
+/// ```
+///
+/// ```text
+/// ################################################# 10/10 (0s)
+/// ```
+pub trait ProgressIterator: Iterator + Sized {
+    /// Wraps this iterator with [`Progress`] using default items.
+    ///
+    /// See [`ProgressIterator`] for an example.
+    fn progress(self) -> ProgressIter<Self>;
+
+    /// Wraps this iterator with [`Progress`] created from given [`ProgressBuilder`].
+    ///
+    /// Total of this iterator is used only if total of
+    /// given `ProgressBuilder` is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{progress_builder, ProgressIterator};
+    ///
+    /// let iter = ["a", "b", "c"]
+    ///     .iter()
+    ///     .progress_with(progress_builder!("[" percent "] " message_fill))?;
+    /// for item in iter {
+    ///     // std::thread::sleep(std::time::Duration::from_millis(500));
+    /// }
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    fn progress_with(self, builder: ProgressBuilder) -> Result<ProgressIter<Self>, Error>;
+}
+
+impl<I: Iterator> ProgressIterator for I {
+    fn progress(self) -> ProgressIter<Self> {
+        self.progress_with(ProgressBuilder::new(Vec::new()))
+            .unwrap()
+    }
+
+    fn progress_with(self, builder: ProgressBuilder) -> Result<ProgressIter<Self>, Error> {
+        let total = match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => u64::try_from(lower).ok(),
+            _ => None,
+        };
+
+        Ok(ProgressIter {
+            iter: self,
+            progress: builder.default_total(total).build()?,
+        })
+    }
+}
+
+// ======================================================================
+// ProgressIter - PUBLIC
+
+/// Iterator wrapped with [`Progress`].
+///
+/// This is created with [`ProgressIterator`].
+pub struct ProgressIter<I> {
+    iter: I,
+    progress: Progress,
+}
+
+impl<I> ProgressIter<I> {
+    /// Returns [`Progress`] of this iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{progress_builder, ProgressIterator};
+    ///
+    /// let iter = ["a", "b", "c"]
+    ///     .into_iter()
+    ///     .progress_with(progress_builder!(pos "/" total " " message_fill))?;
+    /// let progress = iter.as_progress().clone();
+    /// for item in iter {
+    ///     progress.message(item);
+    /// }
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+}
+
+// ======================================================================
+// ProgressIter - IMPL ITERATOR
+
+impl<I: Iterator> Iterator for ProgressIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next();
+        if item.is_some() {
+            self.progress.inc(1);
+        } else {
+            self.progress.finish();
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for ProgressIter<I> {}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::sink_builder;
+
+    #[test]
+    fn exact_size_total() {
+        let iter = (0..10).progress_with(sink_builder()).unwrap();
+        assert_eq!(iter.as_progress().state().lock().total(), Some(10));
+    }
+
+    #[test]
+    fn inexact_size_total() {
+        let iter = (0..10)
+            .filter(|x| x % 2 == 0)
+            .progress_with(sink_builder())
+            .unwrap();
+        assert_eq!(iter.as_progress().state().lock().total(), None);
+    }
+
+    #[test]
+    fn builder_total_is_kept() {
+        let iter = (0..10)
+            .progress_with(sink_builder().total(Some(5)))
+            .unwrap();
+        assert_eq!(iter.as_progress().state().lock().total(), Some(5));
+    }
+
+    #[test]
+    fn finish_when_exhausted() {
+        let mut iter = (0..3)
+            .filter(|x| x % 2 == 0)
+            .progress_with(sink_builder())
+            .unwrap();
+        let progress = iter.as_progress().clone();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(progress.state().lock().pos(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(progress.state().lock().total(), Some(2));
+        assert_eq!(progress.state().lock().percent(), Some(100.0));
+    }
+}
@@ -8,19 +8,48 @@ use std::{
     fmt,
     sync::Arc,
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use parking_lot::Mutex;
 
-pub use crate::state::State;
+#[cfg(feature = "async")]
+pub use crate::async_io::{ProgressAsyncReader, ProgressAsyncWriter, ProgressStream};
+#[cfg(feature = "rayon")]
+pub use crate::par_iter::{ParallelProgressIterator, ProgressParIter};
+pub use crate::{
+    bar::BarStyle,
+    estimator::{AverageEstimator, EmaEstimator, Estimator, WindowEstimator},
+    io::{ProgressReader, ProgressWriter},
+    iter::{ProgressIter, ProgressIterator},
+    multi::MultiProgress,
+    state::State,
+    style::{Color, Style},
+    target::{NonTtyMode, ProgressTarget},
+    template::parse_items,
+    text::Truncate,
+};
 
-use crate::internal::Item;
+use crate::{internal::Item, multi::MultiState, state::Shared};
 
+#[cfg(feature = "async")]
+mod async_io;
+mod bar;
+mod estimator;
 #[allow(missing_docs)]
 pub mod internal;
+mod io;
+mod iter;
 mod macros;
+mod multi;
+#[cfg(feature = "rayon")]
+mod par_iter;
+pub mod spinners;
 mod state;
+mod style;
+mod target;
+mod template;
+mod text;
 
 // ======================================================================
 // CONST - PRIVATE
@@ -31,8 +60,9 @@ const DEFAULT_DRAW_INTERVAL: Duration =
 
 const DEFAULT_DRAW_DELAY: Duration = Duration::from_millis(5);
 
-const MIN_ETA_ELAPSED: Duration = Duration::from_millis(100);
-const MIN_SPEED_ELAPSED: Duration = Duration::from_millis(100);
+const DEFAULT_MIN_FILL_WIDTH: usize = 10;
+
+const NON_TTY_WIDTH: usize = 80;
 
 const BINARY_PREFIXES: &[&str] = &["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
 const DECIMAL_PREFIXES: &[&str] = &["", "k", "M", "G", "T", "P", "E", "Z", "Y"];
@@ -43,19 +73,32 @@ const DECIMAL_PREFIXES: &[&str] = &["", "k", "M", "G", "T", "P", "E", "Z", "Y"];
 /// Represents all possible errors that can occur in this library.
 #[derive(Debug, PartialEq)]
 pub enum Error {
-    /// Given items contain multiple `*_fill` items but at most one is allowed.
+    /// Step of [`NonTtyMode::PercentStep`] is not positive and finite.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use ml_progress::{progress, Error};
+    /// use ml_progress::{progress_builder, Error, NonTtyMode};
     ///
     /// assert_eq!(
-    ///     progress!(10; bar_fill message_fill).err(),
-    ///     Some(Error::MultipleFillItems)
+    ///     progress_builder!()
+    ///         .non_tty(NonTtyMode::PercentStep(0.0))
+    ///         .build()
+    ///         .err(),
+    ///     Some(Error::InvalidPercentStep)
     /// );
     /// ```
-    MultipleFillItems,
+    InvalidPercentStep,
+
+    /// Template given to [`parse_items`] is invalid.
+    ///
+    /// See [`parse_items`] for an example.
+    InvalidTemplate {
+        /// The invalid token, e.g. unknown item with its braces.
+        token: String,
+        /// Byte position of the token in the template.
+        pos: usize,
+    },
 
     /// Given `total` is out-of-range of `u64`.
     ///
@@ -75,8 +118,16 @@ pub enum Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::MultipleFillItems => {
-                write!(f, "got multiple fill items, at most one is allowed")
+            Error::InvalidPercentStep => {
+                write!(f, "percent step is not positive and finite")
+            }
+
+            Error::InvalidTemplate { token, pos } => {
+                write!(
+                    f,
+                    "invalid token `{}` at position {} of template",
+                    token, pos
+                )
             }
 
             Error::TotalIsOutOfRange => {
@@ -103,8 +154,8 @@ impl StdError for Error {}
 ///
 /// `Progress` is drawn
 /// - using background thread to guarantee timely updates
-/// - only if terminal is detected
-/// - to `STDERR` starting with `"\r"`
+/// - only if terminal is detected, unless [`NonTtyMode`] is set
+/// - to [`ProgressTarget`] (default: `STDERR`) starting with `"\r"`
 /// - from the moment `Progress` is created until `Progress` is finished or dropped
 ///
 /// See crate index for [usage](crate#usage) and [examples](crate#examples).
@@ -114,10 +165,94 @@ impl StdError for Error {}
 pub struct Progress {
     // This is `None` only in `Drop::drop`.
     drawer: Option<Arc<JoinHandle<()>>>,
+    shared: Arc<Shared>,
     state: Arc<Mutex<State>>,
+    // This is `Some` if `Progress` is drawn by `MultiProgress`.
+    multi: Option<Arc<Mutex<MultiState>>>,
 }
 
 impl Progress {
+    /// Creates child `Progress` which advances this `Progress`.
+    ///
+    /// - Child has given `total` and default items but it's not drawn.
+    /// - As child progresses from start to 100% completion, position of this
+    ///   `Progress` advances by `weight` steps in proportion,
+    ///   e.g. child with `weight` 30 and `total` 5 advances
+    ///   this `Progress` by 6 steps with each step of its own.
+    /// - Finishing child with [`finish`] advances this `Progress` by
+    ///   remaining part of `weight`.
+    /// - Fractional advancement is included in [`State::percent`],
+    ///   [`State::speed`] and [`State::eta`] of this `Progress`,
+    ///   while [`State::pos`] is rounded down.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(100)?;
+    /// let child = progress.child(50, 4);
+    /// child.inc(1);
+    /// assert_eq!(progress.state().lock().percent(), Some(12.5));
+    /// assert_eq!(progress.state().lock().pos(), 12);
+    /// child.finish();
+    /// assert_eq!(progress.state().lock().pos(), 50);
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [`finish`]: Progress::finish
+    pub fn child(&self, weight: u64, total: u64) -> Progress {
+        let mut state = ProgressBuilder::new(Vec::new())
+            .total(Some(total))
+            .build_state(false)
+            .unwrap();
+        state.set_parent(self.state.clone());
+        let shared = state.shared().clone();
+        let state = Arc::new(Mutex::new(state));
+        self.state.lock().add_child(&state, weight);
+
+        Self {
+            drawer: self.drawer.clone(),
+            shared,
+            state,
+            multi: self.multi.clone(),
+        }
+    }
+
+    /// Sets total of `Progress` to `None`.
+    ///
+    /// See [`set_total`] for details.
+    ///
+    /// [`set_total`]: Progress::set_total
+    pub fn clear_total(&self) {
+        self.set_total(None);
+    }
+
+    /// Decrements position of `Progress`, stopping at zero.
+    ///
+    /// This isn't counted as progress for speed and ETA.
+    ///
+    /// Like [`inc`] this doesn't lock [`State`] of `Progress`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.inc(6);
+    /// progress.dec(2);
+    /// assert_eq!(progress.state().lock().pos(), 4);
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [`inc`]: Progress::inc
+    pub fn dec(&self, steps: u64) {
+        if self.shared.dec(steps) {
+            self.wake();
+        }
+    }
+
     /// Finishes `Progress` with 100% completion.
     ///
     /// - Sets [`State`] of `Progress` to 100% completion.
@@ -206,6 +341,9 @@ impl Progress {
 
     /// Increments position of `Progress`.
     ///
+    /// This doesn't lock [`State`] of `Progress`, so it's cheap enough
+    /// to be called for every step even in tight loops.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -221,11 +359,38 @@ impl Progress {
     /// ##############################-------------------- 6/10 (0s)
     /// ```
     pub fn inc(&self, steps: u64) {
-        self.state.lock().inc(steps, self.drawer.as_ref().unwrap());
+        if self.shared.inc(steps) {
+            self.wake();
+        }
+    }
+
+    /// Increments total of `Progress`, considering `None` total as zero.
+    ///
+    /// See [`set_total`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress_builder;
+    ///
+    /// let progress = progress_builder!().build()?;
+    /// progress.inc_total(3);
+    /// progress.inc_total(2);
+    /// assert_eq!(progress.state().lock().total(), Some(5));
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [`set_total`]: Progress::set_total
+    pub fn inc_total(&self, steps: u64) {
+        let mut state = self.state.lock();
+        let total = state.total().unwrap_or(0).saturating_add(steps);
+        state.set_total(Some(total), self.drawer.as_ref().unwrap());
     }
 
     /// Sets the message shown by item `message_fill`.
     ///
+    /// Like [`inc`] this doesn't lock [`State`] of `Progress`.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -241,14 +406,145 @@ impl Progress {
     /// ```text
     /// 6/10 Hello, World!
     /// ```
+    ///
+    /// [`inc`]: Progress::inc
     pub fn message(&self, message: impl Into<Cow<'static, str>>) {
+        if self.shared.message(message.into()) {
+            self.wake();
+        }
+    }
+
+    /// Pauses `Progress`.
+    ///
+    /// Time spent paused is excluded from [`State::speed`] and [`State::eta`].
+    /// ETA isn't counted down while paused.
+    /// Pause can be shown with item `paused`.
+    ///
+    /// Position can still be changed while paused.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10; pos "/" total " " (paused "(paused)"))?;
+    /// progress.inc(6);
+    /// progress.pause();
+    /// // wait for user
+    /// progress.resume();
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// ```text
+    /// 6/10 (paused)
+    /// ```
+    pub fn pause(&self) {
+        self.state.lock().pause(self.drawer.as_ref().unwrap());
+    }
+
+    /// Prints given line above `Progress` without corrupting it.
+    ///
+    /// - Clears drawn `Progress`.
+    /// - Prints given line with additional `"\n"` to target of `Progress`.
+    /// - Draws `Progress` again.
+    ///
+    /// Line is printed even if `Progress` isn't drawn, e.g. when finished
+    /// or when target isn't a terminal. With [child] `Progress` the line is
+    /// printed above the root `Progress` and with `Progress` added to
+    /// [`MultiProgress`] above the whole block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.inc(6);
+    /// progress.println("Hello, World!");
+    /// progress.finish_at_current_pos();
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// ```text
+    /// Hello, World!
+    /// ##############################-------------------- 6/10 (0s)
+    /// ```
+    ///
+    /// [child]: Progress::child
+    pub fn println(&self, line: impl AsRef<str>) {
+        if let Some(multi) = &self.multi {
+            multi.lock().println(line.as_ref());
+        } else {
+            self.root().lock().println(line.as_ref());
+        }
+    }
+
+    /// Resumes `Progress` paused with [`pause`].
+    ///
+    /// See [`pause`] for an example.
+    ///
+    /// [`pause`]: Progress::pause
+    pub fn resume(&self) {
+        self.state.lock().resume(self.drawer.as_ref().unwrap());
+    }
+
+    /// Sets position of `Progress`.
+    ///
+    /// This isn't counted as progress for speed and ETA.
+    ///
+    /// Like [`inc`] this doesn't lock [`State`] of `Progress`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.set_pos(7);
+    /// assert_eq!(progress.state().lock().pos(), 7);
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [`inc`]: Progress::inc
+    pub fn set_pos(&self, pos: u64) {
+        if self.shared.set_pos(pos) {
+            self.wake();
+        }
+    }
+
+    /// Sets total of `Progress`.
+    ///
+    /// [`State::percent`] and [`State::eta`] are updated to match new total,
+    /// e.g. they become `None` if total is set to `None`.
+    ///
+    /// Unlike [`inc`] this locks [`State`] of `Progress`.
+    /// This does nothing if `Progress` has been finished.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.inc(6);
+    /// progress.set_total(Some(20));
+    /// assert_eq!(progress.state().lock().percent(), Some(30.0));
+    /// progress.set_total(None);
+    /// assert_eq!(progress.state().lock().percent(), None);
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [`inc`]: Progress::inc
+    pub fn set_total(&self, total: Option<u64>) {
         self.state
             .lock()
-            .message(message, self.drawer.as_ref().unwrap());
+            .set_total(total, self.drawer.as_ref().unwrap());
     }
 
     /// Returns current state of `Progress`.
     ///
+    /// [`State`] is updated from latest changes before returning.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -260,18 +556,77 @@ impl Progress {
     /// # Ok::<(), ml_progress::Error>(())
     /// ```
     pub fn state(&self) -> &Arc<Mutex<State>> {
+        if self.state.lock().sync(Instant::now()) {
+            self.wake();
+        }
         &self.state
     }
+
+    /// Calls given function with drawn `Progress` cleared.
+    ///
+    /// - Clears drawn `Progress`.
+    /// - Calls given function, which can e.g. print to `STDERR`.
+    /// - Draws `Progress` again.
+    ///
+    /// `Progress` isn't drawn while given function is running,
+    /// but e.g. [`inc`] doesn't block.
+    /// See [`println`] about [child] `Progress` and [`MultiProgress`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.inc(6);
+    /// progress.suspend(|| eprintln!("Hello, World!"));
+    /// progress.finish_at_current_pos();
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// ```text
+    /// Hello, World!
+    /// ##############################-------------------- 6/10 (0s)
+    /// ```
+    ///
+    /// [child]: Progress::child
+    /// [`inc`]: Progress::inc
+    /// [`println`]: Progress::println
+    pub fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        if let Some(multi) = &self.multi {
+            multi.lock().suspend(f)
+        } else {
+            self.root().lock().suspend(f)
+        }
+    }
 }
 
 impl Drop for Progress {
     fn drop(&mut self) {
+        // Parent keeps contribution of dropped child, so make it up to date.
+        let parent = self.state.lock().parent();
+        if let Some(parent) = parent {
+            if parent.lock().sync(Instant::now()) {
+                self.wake();
+            }
+        }
+
         if let Ok(drawer) = Arc::try_unwrap(self.drawer.take().unwrap()) {
-            let mut state = self.state.lock();
-            if !state.is_finished() {
-                state.finish_quietly(&drawer);
+            // Drawer may be owned by any parent, so finish them all.
+            let mut next = Some(self.state.clone());
+            while let Some(state) = next {
+                let mut state = state.lock();
+                if !state.is_finished() {
+                    state.finish_quietly(&drawer);
+                }
+                next = state.parent();
+            }
+            if let Some(multi) = &self.multi {
+                let mut multi = multi.lock();
+                if !multi.is_finished() {
+                    multi.finish_quietly(&drawer);
+                }
             }
-            drop(state);
             let _ = drawer.join();
         }
     }
@@ -282,38 +637,54 @@ impl Drop for Progress {
 
 impl Progress {
     pub(crate) fn new(state: State) -> Self {
+        let shared = state.shared().clone();
         let state = Arc::new(Mutex::new(state));
+        let drawer = spawn_drawer(state.clone());
 
-        let drawer = thread::spawn({
-            let state = state.clone();
-            move || loop {
-                let mut state = state.lock();
+        Self {
+            drawer: Some(Arc::new(drawer)),
+            shared,
+            state,
+            multi: None,
+        }
+    }
 
-                if state.is_finished() {
-                    break;
-                }
+    // Creates `Progress` which is drawn by `MultiProgress`.
+    pub(crate) fn new_in_multi(
+        state: Arc<Mutex<State>>,
+        drawer: Arc<JoinHandle<()>>,
+        multi: Arc<Mutex<MultiState>>,
+    ) -> Self {
+        let shared = state.lock().shared().clone();
 
-                let timeout = match state.try_draw() {
-                    Ok(()) => None,
-                    Err(timeout) => timeout,
-                };
+        Self {
+            drawer: Some(drawer),
+            shared,
+            state,
+            multi: Some(multi),
+        }
+    }
+}
 
-                drop(state);
+// ======================================================================
+// Progress - PRIVATE
 
-                // NOTE: These may wake spuriously
-                if let Some(timeout) = timeout {
-                    thread::park_timeout(timeout);
-                } else {
-                    thread::park();
-                }
+impl Progress {
+    // Returns state of the root `Progress`, i.e. state of `self` unless it's a child.
+    fn root(&self) -> Arc<Mutex<State>> {
+        let mut root = self.state.clone();
+        loop {
+            let parent = root.lock().parent();
+            match parent {
+                Some(parent) => root = parent,
+                None => return root,
             }
-        });
-
-        Self {
-            drawer: Some(Arc::new(drawer)),
-            state,
         }
     }
+
+    fn wake(&self) {
+        self.drawer.as_ref().unwrap().thread().unpark();
+    }
 }
 
 // ======================================================================
@@ -329,23 +700,80 @@ pub struct ProgressBuilder {
     pre_inc: bool,
     thousands_separator: String,
     items: Vec<Item>,
+    bar_style: BarStyle,
+    bar_colors: (Style, Style),
+    min_fill_width: usize,
+    target: ProgressTarget,
+    non_tty: NonTtyMode,
+    estimator: Box<dyn Estimator>,
 }
 
 impl ProgressBuilder {
+    /// Sets styles of done and remaining part of `bar_fill` item,
+    /// default is no styles.
+    ///
+    /// Styles are shown only in a terminal, see [`Style`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{progress_builder, Color, Style};
+    ///
+    /// let progress = progress_builder!()
+    ///     .bar_colors(Style::new().fg(Color::Green), Style::new().dim())
+    ///     .build()?;
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    pub fn bar_colors(self, done: Style, remaining: Style) -> Self {
+        Self {
+            bar_colors: (done, remaining),
+            ..self
+        }
+    }
+
+    /// Sets characters used to draw `bar_fill` item,
+    /// default is [`BarStyle::ascii`].
+    ///
+    /// See [`BarStyle`] for an example.
+    pub fn bar_style(self, style: BarStyle) -> Self {
+        Self {
+            bar_style: style,
+            ..self
+        }
+    }
+
     /// Creates [`Progress`] using configuration of this `ProgressBuilder`.
     ///
     /// See [custom configuration] for an example.
     ///
     /// [custom configuration]: crate#custom-configuration
     pub fn build(self) -> Result<Progress, Error> {
-        let state = State::new(
-            self.total?,
-            self.pre_inc,
-            self.thousands_separator,
-            self.items,
-        )?;
+        Ok(Progress::new(self.build_state(true)?))
+    }
+
+    /// Sets how speed and ETA are estimated, default is [`AverageEstimator`].
+    ///
+    /// See [`Estimator`] for an example.
+    pub fn estimator(self, estimator: impl Estimator) -> Self {
+        Self {
+            estimator: Box::new(estimator),
+            ..self
+        }
+    }
 
-        Ok(Progress::new(state))
+    /// Sets minimum total width of `*_fill` items, default is 10.
+    ///
+    /// If line doesn't fit otherwise, items with [priority] are dropped
+    /// until `*_fill` items are together at least this wide,
+    /// or at least as wide as sum of their [minimum widths] if that is larger.
+    ///
+    /// [priority]: crate#priority
+    /// [minimum widths]: crate#fill
+    pub fn min_fill_width(self, width: usize) -> Self {
+        Self {
+            min_fill_width: width,
+            ..self
+        }
     }
 
     /// Creates `ProgressBuilder` to configure [`Progress`].
@@ -367,6 +795,23 @@ impl ProgressBuilder {
             pre_inc: false,
             thousands_separator: " ".to_owned(),
             items,
+            bar_style: BarStyle::ascii(),
+            bar_colors: (Style::new(), Style::new()),
+            min_fill_width: DEFAULT_MIN_FILL_WIDTH,
+            target: ProgressTarget::Stderr,
+            non_tty: NonTtyMode::Hidden,
+            estimator: Box::new(AverageEstimator::new()),
+        }
+    }
+
+    /// Sets how `Progress` is shown when target is not a terminal,
+    /// default is [`NonTtyMode::Hidden`].
+    ///
+    /// See [`NonTtyMode`] for an example.
+    pub fn non_tty(self, mode: NonTtyMode) -> Self {
+        Self {
+            non_tty: mode,
+            ..self
         }
     }
 
@@ -411,6 +856,13 @@ impl ProgressBuilder {
         }
     }
 
+    /// Sets where `Progress` is drawn, default is [`ProgressTarget::Stderr`].
+    ///
+    /// See [`ProgressTarget`] for an example.
+    pub fn target(self, target: ProgressTarget) -> Self {
+        Self { target, ..self }
+    }
+
     /// Sets thousands separator, default is space.
     ///
     /// See [custom configuration] for an example.
@@ -442,6 +894,63 @@ impl ProgressBuilder {
     }
 }
 
+// ======================================================================
+// ProgressBuilder - CRATE
+
+impl ProgressBuilder {
+    // Creates `State` which is drawn to target of this builder if `is_drawn`
+    // or not drawn by itself otherwise.
+    //
+    // This fails only if `total` or `non_tty` is invalid,
+    // so builder created with `ProgressBuilder::new` can't fail.
+    pub(crate) fn build_state(self, is_drawn: bool) -> Result<State, Error> {
+        if let NonTtyMode::PercentStep(step) = self.non_tty {
+            if !(step.is_finite() && step > 0.0) {
+                return Err(Error::InvalidPercentStep);
+            }
+        }
+
+        Ok(State::new(
+            self.total?,
+            self.pre_inc,
+            self.thousands_separator,
+            self.items,
+            self.bar_style,
+            self.bar_colors,
+            self.min_fill_width,
+            if is_drawn { Some(self.target) } else { None },
+            self.non_tty,
+            self.estimator,
+        ))
+    }
+
+    // Sets total unless it has already been set.
+    pub(crate) fn default_total(self, total: Option<u64>) -> Self {
+        if let Ok(None) = self.total {
+            Self {
+                total: Ok(total),
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+}
+
+// ======================================================================
+// Draw - CRATE
+
+// State which is drawn by a drawer thread.
+pub(crate) trait Draw: Send + 'static {
+    fn is_finished(&self) -> bool;
+
+    // Returns
+    // - `OK(())` - was drawn
+    // - `Err(None)` - not drawn, no draw scheduled
+    // - `Err(Some(..))` - not drawn, draw is scheduled after returned duration
+    fn try_draw(&mut self) -> Result<(), Option<Duration>>;
+}
+
 // ======================================================================
 // FUNCTIONS - PUBLIC
 
@@ -549,13 +1058,671 @@ pub fn group_digits(mut value: u64, separator: &str) -> String {
     result
 }
 
+/// Truncates given text to at most given width with given mode.
+///
+/// Width is counted in terminal columns, so e.g. CJK characters
+/// and most emoji take two columns. Characters are never split.
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::Truncate;
+///
+/// assert_eq!(ml_progress::truncate("foo/bar/baz", 8, Truncate::Path), "foo…/baz");
+/// ```
+pub fn truncate(text: &str, width: usize, mode: Truncate) -> String {
+    text::truncate_with(text, width, mode)
+}
+
+// ======================================================================
+// FUNCTIONS - CRATE
+
+// Spawns thread which draws given state until it's finished.
+pub(crate) fn spawn_drawer<T: Draw>(state: Arc<Mutex<T>>) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        let mut state = state.lock();
+
+        if state.is_finished() {
+            break;
+        }
+
+        // After a draw another one may already be scheduled, e.g. for animation,
+        // so `try_draw` is called again to get its timeout.
+        let timeout = match state.try_draw() {
+            Ok(()) => continue,
+            Err(timeout) => timeout,
+        };
+
+        drop(state);
+
+        // NOTE: These may wake spuriously
+        if let Some(timeout) = timeout {
+            thread::park_timeout(timeout);
+        } else {
+            thread::park();
+        }
+    })
+}
+
 // ======================================================================
 // TESTS
 
 #[cfg(test)]
 mod tests {
+    use std::{env, io};
+
     use super::*;
 
+    // ============================================================
+    // HELPERS
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().clone()).unwrap()
+        }
+    }
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn progress_to(buffer: &Buffer, width: usize, builder: ProgressBuilder) -> Progress {
+        builder
+            .target(ProgressTarget::writer(buffer.clone(), Some(width)))
+            .build()
+            .unwrap()
+    }
+
+    // Helpers shared with tests of other modules.
+
+    pub(crate) fn pos(progress: &Progress) -> u64 {
+        progress.state().lock().pos()
+    }
+
+    pub(crate) fn sink_builder() -> ProgressBuilder {
+        progress_builder!().target(ProgressTarget::writer(io::sink(), None))
+    }
+
+    pub(crate) fn sink_progress() -> Progress {
+        sink_builder().build().unwrap()
+    }
+
+    // ============================================================
+    // ProgressTarget
+
+    #[test]
+    fn target_writer() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 20, progress_builder!(bar_fill).total(Some(10)));
+        progress.inc(5);
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r##########----------\n"));
+    }
+
+    #[test]
+    fn target_writer_clear() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 4, progress_builder!(bar_fill));
+        progress.finish_and_clear();
+        assert!(buffer.contents().ends_with("\r    \r"));
+    }
+
+    #[test]
+    fn target_writer_not_terminal() {
+        let buffer = Buffer::default();
+        let progress = progress_builder!()
+            .target(ProgressTarget::writer(buffer.clone(), None))
+            .build()
+            .unwrap();
+        progress.finish();
+        assert_eq!(buffer.contents(), "");
+    }
+
+    #[test]
+    fn non_tty_invalid_percent_step() {
+        for step in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+            let result = progress_builder!()
+                .non_tty(NonTtyMode::PercentStep(step))
+                .build();
+            assert_eq!(result.err(), Some(Error::InvalidPercentStep));
+        }
+    }
+
+    // ============================================================
+    // State::elapsed
+
+    #[test]
+    fn elapsed() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 20, progress_builder!(elapsed " " elapsed_hms));
+        progress.finish();
+        assert!(buffer.contents().ends_with("\r0s 0:00             \n"));
+    }
+
+    // ============================================================
+    // BarStyle
+
+    #[test]
+    fn bar_style_of_item_overrides_builder() {
+        const ARROW: BarStyle = BarStyle::ascii().filled("=").empty(" ").head(">");
+
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            10,
+            progress_builder!((bar_fill ARROW) " " pos)
+                .total(Some(2))
+                .bar_style(BarStyle::ascii().caps("[", "]")),
+        );
+        progress.inc(1);
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r====>    1\n"));
+    }
+
+    #[test]
+    fn fixed_width_bar_with_fill_item() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            14,
+            progress_builder!((bar 4) " " message_fill " " (bar 3 BarStyle::ascii().filled("=")))
+                .total(Some(4)),
+        );
+        progress.message("abcdefgh");
+        progress.inc(2);
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r##-- abcde =--\n"));
+    }
+
+    // ============================================================
+    // message_fill
+
+    #[test]
+    fn message_fill_with_wide_characters() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 10, progress_builder!(message_fill "|"));
+        progress.message("日本語日本語");
+        progress.finish();
+        assert!(buffer.contents().ends_with("\r日本語日 |\n"));
+    }
+
+    #[test]
+    fn message_truncation_modes() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            16,
+            progress_builder!((message 6 start) "|" (message_fill middle)),
+        );
+        progress.message("abcdefghijk");
+        progress.finish();
+        assert!(buffer.contents().ends_with("\rfghijk|abcd…hijk\n"));
+    }
+
+    // ============================================================
+    // multiple fill items
+
+    #[test]
+    fn fill_items_split_by_weight() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            10,
+            progress_builder!((bar_fill 2) " " (message_fill 1)).total(Some(10)),
+        );
+        progress.message("abcdef");
+        progress.inc(5);
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r###--- abc\n"));
+    }
+
+    #[test]
+    fn fill_items_with_min_and_max_width() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            12,
+            progress_builder!((fill 1 (..=4) bar_fill) "|" (fill 1 (3..) message_fill))
+                .total(Some(4)),
+        );
+        progress.message("abcdefghij");
+        progress.inc(2);
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r##--|abcdefg\n"));
+    }
+
+    // ============================================================
+    // priority
+
+    #[test]
+    fn lowest_priority_dropped_first() {
+        for (width, expected) in [(12, "\r##--- 5 a bb\n"), (10, "\r##--- 5 bb\n")] {
+            let buffer = Buffer::default();
+            let progress = progress_to(
+                &buffer,
+                width,
+                progress_builder!(bar_fill " " pos (priority 1 " a") (priority 2 " bb") (priority 1 " c"))
+                    .total(Some(10))
+                    .min_fill_width(5),
+            );
+            progress.inc(5);
+            progress.finish_at_current_pos();
+            assert!(buffer.contents().ends_with(expected));
+        }
+    }
+
+    // Fill items within `priority` fail to compile, see `item`.
+    #[test]
+    fn priority_with_non_fill_items() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            20,
+            progress_builder!(
+                bar_fill
+                (priority 1 " " (bar 3) (message 4) (priority 2 (spinner spinners::DOTS)))
+                (priority 1 (|_| "!".to_owned()))
+            )
+            .total(Some(3)),
+        );
+        progress.message("abcdef");
+        progress.inc(3);
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r########## ###abcd⠋!\n"));
+    }
+
+    // ============================================================
+    // spinner
+
+    #[test]
+    fn spinner_is_drawn_without_changes() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 1, progress_builder!(spinner));
+        thread::sleep(Duration::from_millis(500));
+        progress.finish();
+
+        let contents = buffer.contents();
+        for frame in spinners::LINE {
+            assert!(contents.contains(&format!("\r{}", frame)));
+        }
+    }
+
+    // ============================================================
+    // Style
+
+    #[test]
+    fn styles() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            10,
+            progress_builder!((bold "a") " " (red bar_fill))
+                .total(Some(4))
+                .bar_colors(Style::new().fg(Color::Green), Style::new()),
+        );
+        progress.set_pos(2);
+        progress.finish_at_current_pos();
+
+        let expected = if env::var_os("NO_COLOR").map_or(true, |value| value.is_empty()) {
+            "\r\x1b[1ma\x1b[0m \x1b[31m\x1b[32m####\x1b[0m\x1b[31m----\x1b[0m\n"
+        } else {
+            "\ra ####----\n"
+        };
+        assert!(buffer.contents().ends_with(expected));
+    }
+
+    // ============================================================
+    // Progress::set_pos / Progress::set_total
+
+    #[test]
+    fn dec_stops_at_zero() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        progress.inc(3);
+        progress.dec(5);
+        assert_eq!(progress.state().lock().pos(), 0);
+    }
+
+    #[test]
+    fn set_pos_excluded_from_speed() {
+        let progress = progress_builder!().total(Some(1000)).build().unwrap();
+        progress.set_pos(500);
+        thread::sleep(Duration::from_millis(100));
+        progress.inc(1);
+        let state = progress.state().lock();
+        assert!(state.speed().unwrap() < 20.0);
+        assert!(state.eta().unwrap() > Duration::from_secs(20));
+    }
+
+    #[test]
+    fn set_total_switches_between_known_and_unknown() {
+        let progress = progress_builder!().build().unwrap();
+        progress.set_pos(5);
+        assert_eq!(progress.state().lock().percent(), None);
+        progress.inc_total(10);
+        assert_eq!(progress.state().lock().percent(), Some(50.0));
+        progress.clear_total();
+        let state = progress.state().lock();
+        assert_eq!(state.percent(), None);
+        assert_eq!(state.eta(), None);
+    }
+
+    #[test]
+    fn zero_total_is_complete() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 10, progress_builder!(percent));
+        progress.inc_total(0);
+        assert_eq!(progress.state().lock().percent(), Some(100.0));
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r100%      \n"));
+    }
+
+    // ============================================================
+    // Progress::pause / Progress::resume
+
+    #[test]
+    fn pause() {
+        let buffer = Buffer::default();
+        let progress = progress_to(
+            &buffer,
+            6,
+            progress_builder!(pos "/" total (paused "P")).total(Some(9)),
+        );
+        progress.pause();
+        assert!(progress.state().lock().is_paused());
+        progress.finish_at_current_pos();
+        assert!(buffer.contents().ends_with("\r0/9P  \n"));
+    }
+
+    #[test]
+    fn pause_excluded_from_speed() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        progress.pause();
+        thread::sleep(Duration::from_millis(150));
+        progress.resume();
+        progress.inc(1);
+        let state = progress.state().lock();
+        assert!(!state.is_paused());
+        assert_eq!(state.speed(), None);
+    }
+
+    // ============================================================
+    // Progress::println / Progress::suspend
+
+    #[test]
+    fn println() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 4, progress_builder!(pos "/" total).total(Some(9)));
+        progress.inc(1);
+        progress.child(1, 1).println("foo");
+        progress.finish_and_clear();
+        progress.println("bar");
+        assert!(buffer
+            .contents()
+            .ends_with("\r    \rfoo\n\r1/9 \r    \rbar\n"));
+    }
+
+    #[test]
+    fn suspend() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 4, progress_builder!(pos "/" total).total(Some(9)));
+        let result = progress.suspend(|| {
+            buffer.0.lock().extend_from_slice(b"foo\n");
+            42
+        });
+        assert_eq!(result, 42);
+        progress.finish_and_clear();
+        assert!(buffer.contents().ends_with("\r    \rfoo\n\r0/9 \r    \r"));
+    }
+
+    #[test]
+    fn multi_println() {
+        let buffer = Buffer::default();
+        let multi = MultiProgress::with_target(ProgressTarget::writer(buffer.clone(), Some(10)));
+        let a = multi
+            .add(progress_builder!(bar_fill).total(Some(10)))
+            .unwrap();
+        let b = multi
+            .add(progress_builder!(bar_fill).total(Some(10)))
+            .unwrap();
+        thread::sleep(Duration::from_millis(50));
+        b.println("foo");
+        multi.finish();
+        drop(a);
+        assert!(buffer.contents().ends_with(
+            "\r\x1b[1A\x1b[Jfoo\n\r\x1b[J----------\n----------\r\x1b[1A\x1b[J----------\n----------\n"
+        ));
+    }
+
+    // ============================================================
+    // Progress::inc
+
+    #[test]
+    fn inc_while_locked() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        let state = progress.state().lock();
+        progress.inc(3);
+        progress.message("foo");
+        drop(state);
+        assert_eq!(progress.state().lock().pos(), 3);
+    }
+
+    #[test]
+    fn inc_from_threads() {
+        let progress = progress_builder!().total(Some(4000)).build().unwrap();
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| (0..1000).for_each(|_| progress.inc(1)));
+            }
+        });
+        assert_eq!(progress.state().lock().percent(), Some(100.0));
+    }
+
+    #[test]
+    fn inc_wakes_drawer_about_once_per_draw() {
+        let buffer = Buffer::default();
+        let progress = progress_to(&buffer, 10, progress_builder!(pos));
+
+        // Changes made while a draw is queued don't wake the drawer,
+        // so there are at most two wakeups per draw.
+        let start = Instant::now();
+        let mut wakeups = 0;
+        while start.elapsed() < Duration::from_millis(200) {
+            if progress.shared.inc(1) {
+                progress.wake();
+                wakeups += 1;
+            }
+        }
+        assert!(wakeups <= 20, "{} wakeups", wakeups);
+
+        progress.finish();
+        assert!(buffer.contents().matches('\r').count() <= 10);
+    }
+
+    #[test]
+    fn message_replaces_previous_message() {
+        let progress = progress_builder!().build().unwrap();
+        let state = progress.state().lock();
+        progress.message("foo");
+        progress.message("bar");
+        drop(state);
+        assert_eq!(progress.state().lock().message(), "bar");
+    }
+
+    // ============================================================
+    // Progress::child
+
+    #[test]
+    fn child_nested() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        let child = progress.child(4, 2);
+        let grandchild = child.child(1, 4);
+        grandchild.inc(2);
+        assert_eq!(child.state().lock().percent(), Some(25.0));
+        assert_eq!(progress.state().lock().percent(), Some(10.0));
+        grandchild.finish();
+        child.inc(1);
+        assert_eq!(progress.state().lock().pos(), 4);
+    }
+
+    #[test]
+    fn child_after_parent_finish() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        let child = progress.child(5, 5);
+        child.inc(2);
+        progress.finish();
+        child.inc(2);
+        assert_eq!(progress.state().lock().pos(), 10);
+        assert_eq!(progress.state().lock().percent(), Some(100.0));
+    }
+
+    #[test]
+    fn child_dropped_after_parent() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        let child = progress.child(5, 5);
+        drop(progress);
+        child.inc(1);
+        drop(child);
+    }
+
+    #[test]
+    fn child_dropped_keeps_pos() {
+        let progress = progress_builder!().total(Some(10)).build().unwrap();
+        let child = progress.child(6, 4);
+        child.inc(2);
+        drop(child);
+        assert_eq!(progress.state().lock().pos(), 3);
+    }
+
+    // ============================================================
+    // MultiProgress
+
+    #[test]
+    fn multi_progress() {
+        let buffer = Buffer::default();
+        let multi = MultiProgress::with_target(ProgressTarget::writer(buffer.clone(), Some(10)));
+        let a = multi
+            .add(progress_builder!(bar_fill).total(Some(10)))
+            .unwrap();
+        let b = multi
+            .add(progress_builder!(bar_fill).total(Some(10)))
+            .unwrap();
+        let c = multi.add(progress_builder!(bar_fill)).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        a.inc(5);
+        b.finish();
+        c.finish_and_clear();
+        multi.finish();
+        assert!(buffer
+            .contents()
+            .ends_with("\r\x1b[2A\x1b[J#####-----\n##########\n"));
+    }
+
+    #[test]
+    fn multi_progress_dropped_line_is_kept() {
+        let buffer = Buffer::default();
+        let multi = MultiProgress::with_target(ProgressTarget::writer(buffer.clone(), Some(10)));
+        let a = multi
+            .add(progress_builder!(bar_fill).total(Some(10)))
+            .unwrap();
+        a.inc(2);
+        thread::sleep(Duration::from_millis(50));
+        drop(a);
+        multi.finish();
+        assert!(buffer.contents().ends_with("\r\x1b[J##--------\n"));
+    }
+
+    #[test]
+    fn multi_progress_finished_spinner_is_not_redrawn() {
+        let buffer = Buffer::default();
+        let multi = MultiProgress::with_target(ProgressTarget::writer(buffer.clone(), Some(10)));
+        let a = multi.add(progress_builder!(spinner)).unwrap();
+        let _b = multi.add(progress_builder!(pos)).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        a.finish();
+        thread::sleep(Duration::from_millis(50));
+        let draws = buffer.contents().matches('\r').count();
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(buffer.contents().matches('\r').count(), draws);
+    }
+
+    #[test]
+    fn multi_progress_dropped_before_lines() {
+        let multi = MultiProgress::with_target(ProgressTarget::writer(io::sink(), Some(10)));
+        let a = multi.add(progress_builder!()).unwrap();
+        drop(multi);
+        a.inc(1);
+        drop(a);
+    }
+
+    // ============================================================
+    // NonTtyMode
+
+    fn non_tty_progress(buffer: &Buffer, mode: NonTtyMode) -> Progress {
+        progress_builder!(pos "/" total " " message_fill)
+            .total(Some(10))
+            .target(ProgressTarget::writer(buffer.clone(), None))
+            .non_tty(mode)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn non_tty_interval() {
+        let buffer = Buffer::default();
+        let progress = non_tty_progress(&buffer, NonTtyMode::Interval(Duration::from_secs(60)));
+        thread::sleep(Duration::from_millis(50));
+        progress.inc(4);
+        thread::sleep(Duration::from_millis(50));
+        progress.finish();
+        assert_eq!(buffer.contents(), "0/10\n10/10\n");
+    }
+
+    #[test]
+    fn non_tty_percent_step() {
+        let buffer = Buffer::default();
+        let progress = non_tty_progress(&buffer, NonTtyMode::PercentStep(50.0));
+        for _ in 0..10 {
+            thread::sleep(Duration::from_millis(60));
+            progress.inc(1);
+        }
+        thread::sleep(Duration::from_millis(60));
+        progress.message("done");
+        progress.finish_at_current_pos();
+        assert_eq!(buffer.contents(), "0/10\n5/10\n10/10\n10/10 done\n");
+    }
+
+    #[test]
+    fn non_tty_percent_step_after_set_total() {
+        let buffer = Buffer::default();
+        let progress = non_tty_progress(&buffer, NonTtyMode::PercentStep(50.0));
+        thread::sleep(Duration::from_millis(60));
+        progress.set_pos(10);
+        thread::sleep(Duration::from_millis(60));
+        progress.set_total(Some(100));
+        thread::sleep(Duration::from_millis(60));
+        progress.set_pos(50);
+        thread::sleep(Duration::from_millis(60));
+        progress.finish_at_current_pos();
+        assert_eq!(buffer.contents(), "0/10\n10/10\n10/100\n50/100\n");
+    }
+
+    #[test]
+    fn non_tty_finish_and_clear() {
+        let buffer = Buffer::default();
+        let progress = non_tty_progress(&buffer, NonTtyMode::PercentStep(50.0));
+        progress.finish_and_clear();
+        assert_eq!(buffer.contents(), "");
+    }
+
     // ============================================================
     // binary_prefix
 
@@ -113,14 +113,69 @@ macro_rules! items {
 ///
 /// This is used internally by [`items`] macro.
 ///
+/// `*_fill` items, possibly styled or within nested `priority` item,
+/// fail to compile within `priority` item, since they would never get any width.
+///
+/// ```compile_fail
+/// let progress = ml_progress::progress!(10; pos (priority 1 " " bar_fill));
+/// ```
+///
+/// ```compile_fail
+/// let progress = ml_progress::progress!(10; pos (priority 1 (green (message_fill 2))));
+/// ```
+///
 /// [`Item`]: crate::internal::Item
 #[macro_export]
 macro_rules! item {
     // ============================================================
     // BAR
 
+    (( bar $width:literal )) => {
+        $crate::internal::Item::Bar($width, None)
+    };
+
+    (( bar $width:literal $style:expr )) => {
+        $crate::internal::Item::Bar($width, Some($style))
+    };
+
     ( bar_fill ) => {
-        $crate::internal::Item::Fill($crate::internal::FillItem::Bar)
+        $crate::internal::Item::Fill($crate::internal::FillItem::Bar(None))
+    };
+
+    (( bar_fill $weight:literal )) => { $crate::item!(( fill $weight bar_fill )) };
+
+    (( bar_fill $style:expr )) => {
+        $crate::internal::Item::Fill($crate::internal::FillItem::Bar(Some($style)))
+    };
+
+    // ============================================================
+    // ELAPSED
+
+    (  elapsed                  ) => { $crate::item!(( elapsed "{}{}" )) };
+
+    (( elapsed $format:literal )) => {
+        $crate::internal::Item::Fn(Box::new(|s| {
+            let (amount, unit) = $crate::duration_approx(s.elapsed());
+            format!(
+                $format,
+                $crate::internal::FormatInteger::new(amount, s.thousands_separator()),
+                unit,
+            )
+        }))
+    };
+
+    // ============================================================
+    // ELAPSED HMS
+
+    ( elapsed_hms ) => {
+        $crate::internal::Item::Fn(Box::new(|s| {
+            let (h, m, s) = $crate::duration_hms(s.elapsed());
+            if h > 0 {
+                format!("{}:{:02}:{:02}", h, m, s)
+            } else {
+                format!("{}:{:02}", m, s)
+            }
+        }))
     };
 
     // ============================================================
@@ -165,11 +220,60 @@ macro_rules! item {
         }))
     };
 
+    // ============================================================
+    // FILL
+
+    (( fill $weight:literal $item:tt )) => { $crate::item!(( fill $weight (..) $item )) };
+
+    (( fill $weight:literal $range:tt $item:tt )) => {
+        $crate::internal::Item::Weighted(
+            $crate::internal::FillWidth::new($weight, $range),
+            Box::new($crate::item!($item)),
+        )
+    };
+
     // ============================================================
     // MESSAGE
 
-    ( message_fill ) => {
-        $crate::internal::Item::Fill($crate::internal::FillItem::Message)
+    (  message                       ) => { $crate::internal::Item::Fn(Box::new(|s| s.message().to_owned())) };
+    (( message $width:literal        )) => { $crate::item!(( message $width end )) };
+    (( message $width:literal end    )) => { $crate::item!(( message $width ($crate::Truncate::End)    )) };
+    (( message $width:literal start  )) => { $crate::item!(( message $width ($crate::Truncate::Start)  )) };
+    (( message $width:literal middle )) => { $crate::item!(( message $width ($crate::Truncate::Middle) )) };
+    (( message $width:literal path   )) => { $crate::item!(( message $width ($crate::Truncate::Path)   )) };
+
+    (( message $width:literal $mode:expr )) => {
+        $crate::internal::Item::Fn(Box::new(|s| $crate::truncate(s.message(), $width, $mode)))
+    };
+
+    // ============================================================
+    // MESSAGE_FILL
+
+    (  message_fill        ) => { $crate::item!(( message_fill end )) };
+    (( message_fill end    )) => { $crate::item!(( message_fill ($crate::Truncate::End)    )) };
+    (( message_fill start  )) => { $crate::item!(( message_fill ($crate::Truncate::Start)  )) };
+    (( message_fill middle )) => { $crate::item!(( message_fill ($crate::Truncate::Middle) )) };
+    (( message_fill path   )) => { $crate::item!(( message_fill ($crate::Truncate::Path)   )) };
+
+    (( message_fill $weight:literal )) => { $crate::item!(( fill $weight message_fill )) };
+
+    (( message_fill $mode:expr )) => {
+        $crate::internal::Item::Fill($crate::internal::FillItem::Message($mode))
+    };
+
+    // ============================================================
+    // PAUSED
+
+    (  paused               ) => { $crate::item!(( paused "paused" )) };
+
+    (( paused $text:literal )) => {
+        $crate::internal::Item::Fn(Box::new(|s| {
+            if s.is_paused() {
+                $text.to_string()
+            } else {
+                "".to_string()
+            }
+        }))
     };
 
     // ============================================================
@@ -235,6 +339,32 @@ macro_rules! item {
         }))
     };
 
+    // ============================================================
+    // PRIORITY
+
+    (( priority $priority:literal $($item:tt)+ )) => {{
+        $crate::item! { @no_fill $($item)+ }
+        $crate::internal::Item::Priority($priority, $crate::items!($($item)+))
+    }};
+
+    // Fails to compile if any given item is a `*_fill` item.
+    ( @no_fill $($item:tt)+ ) => { $( $crate::item!{ @no_fill_item $item } )+ };
+
+    ( @no_fill_item   bar_fill                   ) => { $crate::item!{ @fill_error } };
+    ( @no_fill_item ( bar_fill     $($arg:tt)* ) ) => { $crate::item!{ @fill_error } };
+    ( @no_fill_item ( fill         $($arg:tt)* ) ) => { $crate::item!{ @fill_error } };
+    ( @no_fill_item   message_fill               ) => { $crate::item!{ @fill_error } };
+    ( @no_fill_item ( message_fill $($arg:tt)* ) ) => { $crate::item!{ @fill_error } };
+
+    ( @no_fill_item ( priority $priority:literal $($item:tt)+ ) ) => { $crate::item!{ @no_fill $($item)+ } };
+    ( @no_fill_item ( style    $style:tt         $item:tt     ) ) => { $crate::item!{ @no_fill_item $item } };
+    ( @no_fill_item ( $style:ident               $item:tt     ) ) => { $crate::item!{ @no_fill_item $item } };
+    ( @no_fill_item   $item:tt                                  ) => {};
+
+    ( @fill_error ) => {
+        compile_error!("`*_fill` items can't be used within `priority` item");
+    };
+
     // ============================================================
     // SPEED
 
@@ -316,6 +446,35 @@ macro_rules! item {
         }))
     };
 
+    // ============================================================
+    // SPINNER
+
+    (  spinner               ) => { $crate::item!(( spinner $crate::spinners::LINE )) };
+
+    (( spinner $frames:expr )) => {
+        $crate::internal::Item::Spinner($frames)
+    };
+
+    // ============================================================
+    // STYLE
+
+    (( style $style:tt $item:tt )) => {
+        $crate::internal::Item::Styled($style, Box::new($crate::item!($item)))
+    };
+
+    (( black     $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Black))   $item )) };
+    (( red       $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Red))     $item )) };
+    (( green     $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Green))   $item )) };
+    (( yellow    $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Yellow))  $item )) };
+    (( blue      $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Blue))    $item )) };
+    (( magenta   $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Magenta)) $item )) };
+    (( cyan      $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::Cyan))    $item )) };
+    (( white     $item:tt )) => { $crate::item!(( style ($crate::Style::new().fg($crate::Color::White))   $item )) };
+    (( bold      $item:tt )) => { $crate::item!(( style ($crate::Style::new().bold())                     $item )) };
+    (( dim       $item:tt )) => { $crate::item!(( style ($crate::Style::new().dim())                      $item )) };
+    (( italic    $item:tt )) => { $crate::item!(( style ($crate::Style::new().italic())                   $item )) };
+    (( underline $item:tt )) => { $crate::item!(( style ($crate::Style::new().underline())                $item )) };
+
     // ============================================================
     // TOTAL
 
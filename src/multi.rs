@@ -0,0 +1,370 @@
+use std::{
+    sync::{Arc, Weak},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::{spawn_drawer, text, Draw, Error, Progress, ProgressBuilder, ProgressTarget, State};
+
+// ======================================================================
+// MultiProgress - PUBLIC
+
+/// Multiple [`Progress`] lines drawn together as a block.
+///
+/// `MultiProgress` is drawn
+/// - using one background thread for all lines
+/// - only if terminal is detected
+/// - to [`ProgressTarget`] (default: `STDERR`),
+///   moving cursor to the first line of the block with ANSI escape codes
+///   and redrawing all lines
+/// - from the moment `MultiProgress` is created
+///   until `MultiProgress` is finished or dropped
+///
+/// Each [`Progress`] is added with [`add`] and then used as usual.
+///
+/// - Line of `Progress` finished with [`finish`] or [`finish_at_current_pos`]
+///   stays in the block with its final state.
+/// - Line of `Progress` finished with [`finish_and_clear`]
+///   is removed from the block.
+/// - Line of `Progress` dropped without finishing
+///   stays in the block with its latest drawn state.
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::{progress_builder, MultiProgress};
+///
+/// let multi = MultiProgress::new();
+/// let a = multi.add(progress_builder!("a " bar_fill " " pos "/" total).total(Some(10)))?;
+/// let b = multi.add(progress_builder!("b " bar_fill " " pos "/" total).total(Some(10)))?;
+/// a.inc(6);
+/// b.inc(3);
+/// a.finish();
+/// b.finish_at_current_pos();
+/// multi.finish();
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// ```text
+/// a ########################################################## 10/10
+/// b #################----------------------------------------- 3/10
+/// ```
+///
+/// [`add`]: MultiProgress::add
+/// [`finish`]: Progress::finish
+/// [`finish_and_clear`]: Progress::finish_and_clear
+/// [`finish_at_current_pos`]: Progress::finish_at_current_pos
+#[derive(Clone)]
+pub struct MultiProgress {
+    // This is `None` only in `Drop::drop`.
+    drawer: Option<Arc<JoinHandle<()>>>,
+    state: Arc<Mutex<MultiState>>,
+}
+
+impl MultiProgress {
+    /// Creates [`Progress`] using configuration of given [`ProgressBuilder`]
+    /// and adds it as the last line of this `MultiProgress`.
+    ///
+    /// Target of given `ProgressBuilder` is ignored,
+    /// `Progress` is drawn by `MultiProgress` instead.
+    ///
+    /// See [`MultiProgress`] for an example.
+    pub fn add(&self, builder: ProgressBuilder) -> Result<Progress, Error> {
+        let state = Arc::new(Mutex::new(builder.build_state(false)?));
+        self.state.lock().add(&state, self.drawer.as_ref().unwrap());
+
+        Ok(Progress::new_in_multi(
+            state,
+            self.drawer.as_ref().unwrap().clone(),
+            self.state.clone(),
+        ))
+    }
+
+    /// Finishes `MultiProgress`.
+    ///
+    /// - Draws all lines once with additional `"\n"`
+    ///   to move cursor to next line after the block.
+    /// - Finishes `MultiProgress`, i.e. there will be no further draws.
+    ///
+    /// Lines of [`Progress`] which haven't been finished
+    /// are drawn with their current state.
+    ///
+    /// See [`MultiProgress`] for an example.
+    pub fn finish(&self) {
+        self.state.lock().finish(self.drawer.as_ref().unwrap());
+    }
+
+    /// Creates `MultiProgress` drawn to `STDERR`.
+    pub fn new() -> Self {
+        Self::with_target(ProgressTarget::Stderr)
+    }
+
+    /// Prints given line above the block without corrupting it.
+    ///
+    /// - Clears drawn block.
+    /// - Prints given line with additional `"\n"` to target of `MultiProgress`.
+    /// - Draws the block again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{progress_builder, MultiProgress};
+    ///
+    /// let multi = MultiProgress::new();
+    /// let a = multi.add(progress_builder!().total(Some(10)))?;
+    /// multi.println("Hello, World!");
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    pub fn println(&self, line: impl AsRef<str>) {
+        self.state.lock().println(line.as_ref());
+    }
+
+    /// Calls given function with drawn block cleared.
+    ///
+    /// - Clears drawn block.
+    /// - Calls given function, which can e.g. print to `STDERR`.
+    /// - Draws the block again.
+    ///
+    /// `MultiProgress` isn't drawn while given function is running.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{progress_builder, MultiProgress};
+    ///
+    /// let multi = MultiProgress::new();
+    /// let a = multi.add(progress_builder!().total(Some(10)))?;
+    /// multi.suspend(|| eprintln!("Hello, World!"));
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    pub fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.state.lock().suspend(f)
+    }
+
+    /// Creates `MultiProgress` drawn to given target.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{MultiProgress, ProgressTarget};
+    ///
+    /// let multi = MultiProgress::with_target(ProgressTarget::Stdout);
+    /// ```
+    pub fn with_target(target: ProgressTarget) -> Self {
+        let state = Arc::new(Mutex::new(MultiState::new(target)));
+        let drawer = spawn_drawer(state.clone());
+
+        Self {
+            drawer: Some(Arc::new(drawer)),
+            state,
+        }
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MultiProgress {
+    fn drop(&mut self) {
+        if let Ok(drawer) = Arc::try_unwrap(self.drawer.take().unwrap()) {
+            let mut state = self.state.lock();
+            if !state.is_finished() {
+                state.finish_quietly(&drawer);
+            }
+            drop(state);
+            let _ = drawer.join();
+        }
+    }
+}
+
+// ======================================================================
+// MultiState - CRATE
+
+pub(crate) struct MultiState {
+    lines: Vec<Line>,
+    target: ProgressTarget,
+    // Number of lines drawn previously, i.e. height of the block.
+    drawn_lines: usize,
+
+    next_draw: Option<Instant>,
+    is_finished: bool,
+}
+
+impl MultiState {
+    pub(crate) fn finish_quietly(&mut self, drawer: &JoinHandle<()>) {
+        if !self.is_finished {
+            self.is_finished = true;
+            drawer.thread().unpark();
+        }
+    }
+
+    pub(crate) fn println(&mut self, line: &str) {
+        let is_cleared = self.clear();
+        self.target.write_str(&format!("{}\n", line));
+        if is_cleared {
+            self.draw(Instant::now());
+        }
+    }
+
+    pub(crate) fn suspend<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let is_cleared = self.clear();
+        let result = f();
+        if is_cleared {
+            self.draw(Instant::now());
+        }
+        result
+    }
+}
+
+// ======================================================================
+// MultiState - IMPL DRAW
+
+impl Draw for MultiState {
+    fn is_finished(&self) -> bool {
+        self.is_finished
+    }
+
+    fn try_draw(&mut self) -> Result<(), Option<Duration>> {
+        assert!(!self.is_finished);
+
+        let now = Instant::now();
+        let mut next_draw = self.next_draw();
+
+        // While a draw is queued, changes are synced only when it's due,
+        // so that they don't wake the drawer in the meantime.
+        if next_draw.map_or(true, |next_draw| next_draw <= now) {
+            for line in &self.lines {
+                if let Some(state) = line.state.upgrade() {
+                    state.lock().sync(now);
+                }
+            }
+            next_draw = self.next_draw();
+        }
+
+        if let Some(next_draw) = next_draw {
+            if next_draw > now {
+                Err(Some(next_draw - now))
+            } else {
+                self.draw(now);
+                Ok(())
+            }
+        } else {
+            Err(None)
+        }
+    }
+}
+
+// ======================================================================
+// MultiState - PRIVATE
+
+impl MultiState {
+    fn add(&mut self, state: &Arc<Mutex<State>>, drawer: &JoinHandle<()>) {
+        self.lines.push(Line {
+            state: Arc::downgrade(state),
+            text: String::new(),
+        });
+        if !self.is_finished {
+            self.next_draw = Some(Instant::now());
+            drawer.thread().unpark();
+        }
+    }
+
+    // Clears drawn block, returning `true` if block was cleared.
+    fn clear(&mut self) -> bool {
+        if !self.is_finished && self.drawn_lines > 0 {
+            self.target.write_str(&self.clear_str());
+            self.drawn_lines = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Returns string which moves cursor to start of drawn block and clears it.
+    fn clear_str(&self) -> String {
+        let mut s = String::from("\r");
+        if self.drawn_lines > 1 {
+            s.push_str(&format!("\x1b[{}A", self.drawn_lines - 1));
+        }
+        s.push_str("\x1b[J");
+        s
+    }
+
+    fn draw(&mut self, now: Instant) {
+        self.next_draw = None;
+
+        let Some(width) = self.target.width() else {
+            return;
+        };
+
+        self.lines.retain_mut(|line| {
+            if let Some(state) = line.state.upgrade() {
+                let mut state = state.lock();
+                state.sync(now);
+                if state.is_cleared() {
+                    return false;
+                }
+                line.text = state.multi_line(width, now);
+            }
+            true
+        });
+
+        let mut output = self.clear_str();
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                output.push('\n');
+            }
+            output.push_str(&text::truncate(&line.text, width));
+        }
+
+        self.target.write_str(&output);
+        self.drawn_lines = self.lines.len();
+    }
+
+    fn finish(&mut self, drawer: &JoinHandle<()>) {
+        if !self.is_finished {
+            self.is_finished = true;
+            drawer.thread().unpark();
+
+            self.draw(Instant::now());
+            if self.drawn_lines > 0 {
+                self.target.write_str("\n");
+            }
+        }
+    }
+
+    fn new(target: ProgressTarget) -> Self {
+        Self {
+            lines: Vec::new(),
+            target,
+            drawn_lines: 0,
+
+            next_draw: None,
+            is_finished: false,
+        }
+    }
+
+    // Returns earliest draw scheduled for block or any line.
+    fn next_draw(&self) -> Option<Instant> {
+        self.lines
+            .iter()
+            .filter_map(|line| line.state.upgrade()?.lock().next_draw())
+            .chain(self.next_draw)
+            .min()
+    }
+}
+
+// ======================================================================
+// Line - PRIVATE
+
+struct Line {
+    // This can't be upgraded after all clones of `Progress` have been dropped.
+    state: Weak<Mutex<State>>,
+    // Latest drawn text.
+    text: String,
+}
@@ -0,0 +1,250 @@
+use std::time::Instant;
+
+use rayon::iter::{
+    plumbing::{Consumer, Folder, UnindexedConsumer},
+    ParallelIterator,
+};
+
+use crate::{Error, Progress, ProgressBuilder, DEFAULT_DRAW_INTERVAL};
+
+// ======================================================================
+// ParallelProgressIterator - PUBLIC
+
+/// Extension trait to wrap any rayon [`ParallelIterator`] with [`Progress`].
+///
+/// This is the parallel version of [`ProgressIterator`],
+/// available with `rayon` feature.
+///
+/// - Total is taken from [`ParallelIterator::opt_len`] if it's known,
+///   which is always the case with `IndexedParallelIterator`.
+/// - Each worker counts its items and calls [`Progress::inc`]
+///   at most once per draw interval, and once more when it's done,
+///   so workers don't contend on [`State`] of `Progress`.
+/// - [`Progress::finish`] is called when iterator has been driven to completion.
+///
+/// Returned iterator is not indexed, so e.g. `zip` and `enumerate`
+/// should be used before `progress`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rayon::prelude::*;
+/// use ml_progress::ParallelProgressIterator;
+///
+/// let sum: u64 = (0..1_000_000u64).into_par_iter().progress().sum();
+/// ```
+///
+/// [`ProgressIterator`]: crate::ProgressIterator
+/// [`State`]: crate::State
+pub trait ParallelProgressIterator: ParallelIterator {
+    /// Wraps this iterator with [`Progress`] using default items.
+    ///
+    /// See [`ParallelProgressIterator`] for an example.
+    fn progress(self) -> ProgressParIter<Self>;
+
+    /// Wraps this iterator with [`Progress`] created from given [`ProgressBuilder`].
+    ///
+    /// Total of this iterator is used only if total of
+    /// given `ProgressBuilder` is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rayon::prelude::*;
+    /// use ml_progress::{progress_builder, ParallelProgressIterator};
+    ///
+    /// let squares: Vec<u64> = (0..1000u64)
+    ///     .into_par_iter()
+    ///     .progress_with(progress_builder!("[" percent "] " bar_fill))?
+    ///     .map(|x| x * x)
+    ///     .collect();
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    fn progress_with(self, builder: ProgressBuilder) -> Result<ProgressParIter<Self>, Error>;
+}
+
+impl<I: ParallelIterator> ParallelProgressIterator for I {
+    fn progress(self) -> ProgressParIter<Self> {
+        self.progress_with(ProgressBuilder::new(Vec::new()))
+            .unwrap()
+    }
+
+    fn progress_with(self, builder: ProgressBuilder) -> Result<ProgressParIter<Self>, Error> {
+        let total = self.opt_len().and_then(|len| u64::try_from(len).ok());
+
+        Ok(ProgressParIter {
+            base: self,
+            progress: builder.default_total(total).build()?,
+        })
+    }
+}
+
+// ======================================================================
+// ProgressParIter - PUBLIC
+
+/// Parallel iterator wrapped with [`Progress`].
+///
+/// This is created with [`ParallelProgressIterator`].
+pub struct ProgressParIter<I> {
+    base: I,
+    progress: Progress,
+}
+
+impl<I> ProgressParIter<I> {
+    /// Returns [`Progress`] of this iterator.
+    pub fn as_progress(&self) -> &Progress {
+        &self.progress
+    }
+}
+
+// ======================================================================
+// ProgressParIter - IMPL PARALLEL ITERATOR
+
+impl<I: ParallelIterator> ParallelIterator for ProgressParIter<I> {
+    type Item = I::Item;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let result = self.base.drive_unindexed(ProgressConsumer {
+            base: consumer,
+            progress: &self.progress,
+        });
+        self.progress.finish();
+        result
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.base.opt_len()
+    }
+}
+
+// ======================================================================
+// ProgressConsumer - PRIVATE
+
+struct ProgressConsumer<'a, C> {
+    base: C,
+    progress: &'a Progress,
+}
+
+impl<'a, T, C: Consumer<T>> Consumer<T> for ProgressConsumer<'a, C> {
+    type Folder = ProgressFolder<'a, C::Folder>;
+    type Reducer = C::Reducer;
+    type Result = C::Result;
+
+    fn split_at(self, index: usize) -> (Self, Self, Self::Reducer) {
+        let (left, right, reducer) = self.base.split_at(index);
+        (
+            ProgressConsumer {
+                base: left,
+                progress: self.progress,
+            },
+            ProgressConsumer {
+                base: right,
+                progress: self.progress,
+            },
+            reducer,
+        )
+    }
+
+    fn into_folder(self) -> Self::Folder {
+        ProgressFolder {
+            base: self.base.into_folder(),
+            progress: self.progress,
+            steps: 0,
+            prev_inc: Instant::now(),
+        }
+    }
+
+    fn full(&self) -> bool {
+        self.base.full()
+    }
+}
+
+impl<'a, T, C: UnindexedConsumer<T>> UnindexedConsumer<T> for ProgressConsumer<'a, C> {
+    fn split_off_left(&self) -> Self {
+        ProgressConsumer {
+            base: self.base.split_off_left(),
+            progress: self.progress,
+        }
+    }
+
+    fn to_reducer(&self) -> Self::Reducer {
+        self.base.to_reducer()
+    }
+}
+
+// ======================================================================
+// ProgressFolder - PRIVATE
+
+struct ProgressFolder<'a, F> {
+    base: F,
+    progress: &'a Progress,
+    // Steps not yet given to `Progress::inc`.
+    steps: u64,
+    prev_inc: Instant,
+}
+
+impl<'a, T, F: Folder<T>> Folder<T> for ProgressFolder<'a, F> {
+    type Result = F::Result;
+
+    fn consume(mut self, item: T) -> Self {
+        self.base = self.base.consume(item);
+        self.steps += 1;
+
+        let now = Instant::now();
+        if now - self.prev_inc >= DEFAULT_DRAW_INTERVAL {
+            self.progress.inc(self.steps);
+            self.steps = 0;
+            self.prev_inc = now;
+        }
+
+        self
+    }
+
+    fn complete(self) -> Self::Result {
+        if self.steps > 0 {
+            self.progress.inc(self.steps);
+        }
+        self.base.complete()
+    }
+
+    fn full(&self) -> bool {
+        self.base.full()
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::*;
+    use crate::tests::sink_builder;
+
+    #[test]
+    fn indexed_total_and_finish() {
+        let iter = (0..10_000u64)
+            .into_par_iter()
+            .progress_with(sink_builder())
+            .unwrap();
+        let progress = iter.as_progress().clone();
+        assert_eq!(progress.state().lock().total(), Some(10_000));
+        assert_eq!(iter.map(|x| x * 2).sum::<u64>(), 99_990_000);
+        assert_eq!(progress.state().lock().pos(), 10_000);
+        assert_eq!(progress.state().lock().percent(), Some(100.0));
+    }
+
+    #[test]
+    fn unindexed_counts_all_items() {
+        let iter = (0..10_000u64)
+            .into_par_iter()
+            .filter(|x| x % 2 == 0)
+            .progress_with(sink_builder())
+            .unwrap();
+        let progress = iter.as_progress().clone();
+        assert_eq!(progress.state().lock().total(), None);
+        assert_eq!(iter.count(), 5_000);
+        assert_eq!(progress.state().lock().pos(), 5_000);
+    }
+}
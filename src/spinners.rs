@@ -0,0 +1,18 @@
+//! Frame sets for `spinner` item.
+//!
+//! Any `&'static [&'static str]` can be used as frame set,
+//! see [spinner] for an example.
+//!
+//! [spinner]: crate#spinner
+
+// ======================================================================
+// CONST - PUBLIC
+
+/// Rotating arc `◜◠◝◞◡◟`.
+pub const ARC: &[&str] = &["◜", "◠", "◝", "◞", "◡", "◟"];
+
+/// Braille dots `⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏`.
+pub const DOTS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Rotating line `-\|/`, this is the default.
+pub const LINE: &[&str] = &["-", "\\", "|", "/"];
@@ -1,14 +1,22 @@
 use std::{
     borrow::Cow,
+    cmp::Reverse,
+    env,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Weak,
+    },
     thread::JoinHandle,
     time::{Duration, Instant},
 };
 
-use terminal_size::Width;
+use crossbeam_queue::ArrayQueue;
+use parking_lot::Mutex;
 
 use crate::{
-    internal::{FillItem, Item},
-    Error, DEFAULT_DRAW_DELAY, DEFAULT_DRAW_INTERVAL, MIN_ETA_ELAPSED, MIN_SPEED_ELAPSED,
+    internal::{self, FillItem, FillWidth, Item},
+    text, BarStyle, Draw, Estimator, NonTtyMode, ProgressTarget, Style, DEFAULT_DRAW_DELAY,
+    DEFAULT_DRAW_INTERVAL, NON_TTY_WIDTH,
 };
 
 // ======================================================================
@@ -18,13 +26,22 @@ use crate::{
 ///
 /// This is used with [custom item] and returned by [`Progress::state`].
 ///
+/// `State` is a snapshot which is updated from latest [`inc`] and [`message`]
+/// calls whenever `Progress` is drawn and when it's returned by [`Progress::state`].
+///
 /// See [custom item] for an example.
 ///
 /// [custom item]: crate#custom-item
 /// [`Progress`]: crate::Progress
 /// [`Progress::state`]: crate::Progress::state
+/// [`inc`]: crate::Progress::inc
+/// [`message`]: crate::Progress::message
 pub struct State {
+    shared: Arc<Shared>,
+
     pos: u64,
+    // Position contributed by children, see `Progress::child`.
+    child_pos: f64,
     total: Option<u64>,
     percent: Option<f64>,
     pre_inc: bool,
@@ -32,41 +49,127 @@ pub struct State {
     message: Cow<'static, str>,
 
     start_time: Instant,
+    finish_time: Option<Instant>,
+    estimator: Box<dyn Estimator>,
+    // Net steps of absolute jumps with `set_pos` and `dec`, which are excluded
+    // from samples given to estimator so that they don't count as progress.
+    jumped: f64,
+    // Time and completed steps, excluding `jumped`, of latest sample given to estimator.
+    latest_sample: (Instant, f64),
     speed: Option<f64>,
     eta_instant: Option<Instant>,
 
+    paused_at: Option<Instant>,
+    // Total duration of finished pauses.
+    paused: Duration,
+
+    parent: Option<Arc<Mutex<State>>>,
+    children: Vec<Child>,
+
     items: Vec<Item>,
+    bar_style: BarStyle,
+    // Styles of done and remaining part of bar.
+    bar_colors: (Style, Style),
+    // This is `false` if `NO_COLOR` is set.
+    is_styled: bool,
+    // Fill item is kept at least this wide by dropping items with priority.
+    min_fill_width: usize,
+    // This is `None` if `State` is not drawn by itself, e.g. with `MultiProgress`.
+    target: Option<ProgressTarget>,
+    non_tty: NonTtyMode,
+    // Latest percent step printed in `NonTtyMode::PercentStep`.
+    non_tty_step: Option<u64>,
+    non_tty_line: String,
 
     prev_draw: Option<Instant>,
     next_draw: Option<Instant>,
     is_finished: bool,
+    is_cleared: bool,
 }
 
 impl State {
+    /// Returns time elapsed since `Progress` was created,
+    /// excluding time spent [paused].
+    ///
+    /// Elapsed time stops when `Progress` is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.finish();
+    /// let elapsed = progress.state().lock().elapsed();
+    /// assert_eq!(progress.state().lock().elapsed(), elapsed);
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [paused]: crate::Progress::pause
+    pub fn elapsed(&self) -> Duration {
+        let now = self.finish_time.unwrap_or_else(Instant::now);
+        self.unpaused_time(now) - self.start_time
+    }
+
     /// Returns estimated time remaining or `None` if estimate is not available.
     ///
-    /// Estimate is based on completed steps and time of latest completion.
+    /// Estimate is made by [`Estimator`] set with [`ProgressBuilder::estimator`]
+    /// and it's counted from time of latest update with changed position.
     ///
     /// Estimate is available if
     /// - [`total`] is `Some` and
-    /// - at least one step and at most [`total`] steps have been completed and
-    /// - at least 100 ms has elapsed since [`Progress`] creation.
+    /// - at most [`total`] steps have been completed and
+    /// - estimator returns an estimate, see e.g. [`AverageEstimator`].
     ///
     /// See [custom item] for an example.
     ///
     /// [custom item]: crate#custom-item
-    /// [`Progress`]: crate::Progress
+    /// [`AverageEstimator`]: crate::AverageEstimator
+    /// [`Estimator`]: crate::Estimator
+    /// [`ProgressBuilder::estimator`]: crate::ProgressBuilder::estimator
     /// [`total`]: State::total
     pub fn eta(&self) -> Option<Duration> {
         if self.is_finished {
             Some(Duration::ZERO)
         } else if let Some(eta) = self.eta_instant {
-            eta.checked_duration_since(Instant::now())
+            eta.checked_duration_since(self.paused_at.unwrap_or_else(Instant::now))
         } else {
             None
         }
     }
 
+    /// Returns `true` if `Progress` is paused.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.pause();
+    /// assert!(progress.state().lock().is_paused());
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Returns message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::progress;
+    ///
+    /// let progress = progress!(10)?;
+    /// progress.message("foo");
+    /// assert_eq!(progress.state().lock().message(), "foo");
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
     /// Returns percentual completion or `None` if [`total`] is `None`.
     ///
     /// Returned value can be over 100 if [`position`]
@@ -91,6 +194,8 @@ impl State {
 
     /// Returns position.
     ///
+    /// Position includes position contributed by [children], rounded down.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -101,21 +206,22 @@ impl State {
     /// assert_eq!(progress.state().lock().pos(), 6);
     /// # Ok::<(), ml_progress::Error>(())
     /// ```
+    ///
+    /// [children]: crate::Progress::child
     pub fn pos(&self) -> u64 {
-        self.pos
+        self.pos + self.child_pos as u64
     }
 
     /// Returns speed in steps per second
     /// or `None` if speed is not available.
     ///
-    /// Speed is average from when [`Progress`] was created until latest [`inc`].
-    ///
-    /// Speed is available if
-    /// - at least one step has been completed and
-    /// - at least 100 ms has elapsed since [`Progress`] creation.
+    /// Speed is estimated by [`Estimator`] set with [`ProgressBuilder::estimator`]
+    /// and it's updated whenever `State` is updated with changed position.
+    /// See e.g. [`AverageEstimator`] about when speed is available.
     ///
-    /// [`Progress`]: crate::Progress
-    /// [`inc`]: crate::Progress::inc
+    /// [`AverageEstimator`]: crate::AverageEstimator
+    /// [`Estimator`]: crate::Estimator
+    /// [`ProgressBuilder::estimator`]: crate::ProgressBuilder::estimator
     pub fn speed(&self) -> Option<f64> {
         self.speed
     }
@@ -160,46 +266,62 @@ impl State {
 // State - CRATE
 
 impl State {
+    // Adds child whose `State` advances position of this `State` by `weight` steps
+    // as child progresses from start to 100% completion.
+    pub(crate) fn add_child(&mut self, child: &Arc<Mutex<State>>, weight: u64) {
+        self.children.push(Child {
+            state: Arc::downgrade(child),
+            weight,
+            pos: 0.0,
+        });
+    }
+
     pub(crate) fn finish(&mut self, drawer: &JoinHandle<()>) {
         if !self.is_finished {
+            self.sync(Instant::now());
             if let Some(total) = self.total {
                 self.pos = total;
             } else {
+                self.pos = self.pos();
                 self.total = Some(self.pos);
             }
+            self.child_pos = 0.0;
             self.percent = Some(100.0);
             self.eta_instant = None;
             self.is_finished = true;
+            self.finish_time = Some(Instant::now());
+            self.next_draw = Some(Instant::now());
             drawer.thread().unpark();
 
-            self.draw();
-            if terminal_size::terminal_size().is_some() {
-                eprintln!();
-            }
+            self.draw_final();
         }
     }
 
     pub(crate) fn finish_and_clear(&mut self, drawer: &JoinHandle<()>) {
         if !self.is_finished {
             self.is_finished = true;
+            self.finish_time = Some(Instant::now());
+            self.is_cleared = true;
+            self.next_draw = Some(Instant::now());
             drawer.thread().unpark();
 
-            if let Some((Width(width), _)) = terminal_size::terminal_size() {
-                let width = width as usize;
-                eprint!("\r{:width$.width$}\r", "");
+            if let Some(target) = &mut self.target {
+                if let Some(width) = target.width() {
+                    target.write_str(&format!("\r{:width$.width$}\r", ""));
+                }
             }
         }
     }
 
     pub(crate) fn finish_at_current_pos(&mut self, drawer: &JoinHandle<()>) {
         if !self.is_finished {
+            self.sync(Instant::now());
             self.is_finished = true;
+            self.finish_time = Some(Instant::now());
+            self.next_draw = Some(Instant::now());
             drawer.thread().unpark();
 
-            self.draw();
-            if terminal_size::terminal_size().is_some() {
-                eprintln!();
-            }
+            self.draw_final();
         }
     }
 
@@ -210,107 +332,231 @@ impl State {
     pub(crate) fn finish_quietly(&mut self, drawer: &JoinHandle<()>) {
         if !self.is_finished {
             self.is_finished = true;
+            self.finish_time = Some(Instant::now());
             drawer.thread().unpark();
         }
     }
 
-    pub(crate) fn is_finished(&self) -> bool {
-        self.is_finished
+    pub(crate) fn is_cleared(&self) -> bool {
+        self.is_cleared
     }
 
-    pub(crate) fn inc(&mut self, steps: u64, drawer: &JoinHandle<()>) {
+    // Returns line for drawing by `MultiProgress`,
+    // with any scheduled draw considered done.
+    pub(crate) fn multi_line(&mut self, width: usize, now: Instant) -> String {
+        self.prev_draw = Some(now);
+        self.next_draw = self.is_animated().then(|| now + DEFAULT_DRAW_INTERVAL);
+        self.line(width, true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        total: Option<u64>,
+        pre_inc: bool,
+        thousands_separator: String,
+        items: Vec<Item>,
+        bar_style: BarStyle,
+        bar_colors: (Style, Style),
+        min_fill_width: usize,
+        target: Option<ProgressTarget>,
+        non_tty: NonTtyMode,
+        mut estimator: Box<dyn Estimator>,
+    ) -> Self {
         let now = Instant::now();
-        let elapsed = now - self.start_time;
+        estimator.add(now, 0.0);
+
+        Self {
+            shared: Arc::new(Shared {
+                pos: AtomicU64::new(0),
+                jumped: AtomicU64::new(0),
+                message: ArrayQueue::new(1),
+                is_changed: AtomicBool::new(false),
+            }),
+
+            pos: 0,
+            child_pos: 0.0,
+            total,
+            percent: if total.is_none() { None } else { Some(0.0) },
+            pre_inc,
+            thousands_separator,
+            message: Cow::Borrowed(""),
+
+            start_time: now,
+            finish_time: None,
+            estimator,
+            jumped: 0.0,
+            latest_sample: (now, 0.0),
+            speed: None,
+            eta_instant: None,
+
+            paused_at: None,
+            paused: Duration::ZERO,
+
+            parent: None,
+            children: Vec::new(),
+
+            items: items.into_iter().map(Item::for_locale).collect(),
+            bar_style: bar_style.for_locale(),
+            bar_colors,
+            is_styled: env::var_os("NO_COLOR").map_or(true, |value| value.is_empty()),
+            min_fill_width,
+            target,
+            non_tty,
+            non_tty_step: None,
+            non_tty_line: String::new(),
+
+            prev_draw: None,
+            next_draw: Some(now + DEFAULT_DRAW_DELAY),
+            is_finished: false,
+            is_cleared: false,
+        }
+    }
 
-        self.pos += steps;
+    pub(crate) fn next_draw(&self) -> Option<Instant> {
+        self.next_draw
+    }
 
-        let completed = if self.pre_inc {
-            self.pos.saturating_sub(1)
-        } else {
-            self.pos
-        };
+    pub(crate) fn parent(&self) -> Option<Arc<Mutex<State>>> {
+        self.parent.clone()
+    }
 
-        if elapsed >= MIN_SPEED_ELAPSED && completed > 0 {
-            self.speed = Some(completed as f64 / elapsed.as_secs_f64());
+    pub(crate) fn pause(&mut self, drawer: &JoinHandle<()>) {
+        if self.paused_at.is_none() {
+            let now = Instant::now();
+            self.sync(now);
+            self.paused_at = Some(now);
+            if self.queue_draw(now) {
+                drawer.thread().unpark();
+            }
         }
+    }
 
-        if let Some(total) = self.total {
-            self.percent = Some(completed as f64 / total as f64 * 100.0);
+    pub(crate) fn println(&mut self, line: &str) {
+        let is_cleared = self.clear_line();
+        self.write_str(&format!("{}\n", line));
+        if is_cleared {
+            self.sync(Instant::now());
+            self.draw();
+        }
+    }
 
-            if completed > total {
-                self.eta_instant = None;
-            } else if elapsed >= MIN_ETA_ELAPSED && completed > 0 {
-                let duration = elapsed.mul_f64(total as f64 / completed as f64);
-                self.eta_instant = Some(self.start_time + duration);
+    pub(crate) fn resume(&mut self, drawer: &JoinHandle<()>) {
+        if let Some(paused_at) = self.paused_at.take() {
+            let now = Instant::now();
+            let pause = now - paused_at;
+            self.paused += pause;
+            self.latest_sample.0 += pause;
+            self.eta_instant = self.eta_instant.map(|eta| eta + pause);
+            if self.queue_draw(now) {
+                drawer.thread().unpark();
             }
         }
-
-        self.queue_draw(now, drawer);
     }
 
-    pub(crate) fn message(
-        &mut self,
-        message: impl Into<Cow<'static, str>>,
-        drawer: &JoinHandle<()>,
-    ) {
-        self.message = message.into();
-        self.queue_draw(Instant::now(), drawer);
+    pub(crate) fn set_parent(&mut self, parent: Arc<Mutex<State>>) {
+        self.parent = Some(parent);
     }
 
-    pub(crate) fn new(
-        total: Option<u64>,
-        pre_inc: bool,
-        thousands_separator: String,
-        items: Vec<Item>,
-    ) -> Result<Self, Error> {
-        let mut fill_item_count = 0;
-        for item in &items {
-            if let Item::Fill(_) = item {
-                fill_item_count += 1;
+    pub(crate) fn set_total(&mut self, total: Option<u64>, drawer: &JoinHandle<()>) {
+        if !self.is_finished {
+            let now = Instant::now();
+            self.sync(now);
+            self.total = total;
+            // Steps of previous total don't apply to new one.
+            self.non_tty_step = None;
+            self.update(now);
+            if self.queue_draw(now) {
+                drawer.thread().unpark();
             }
         }
+    }
 
-        if fill_item_count > 1 {
-            Err(Error::MultipleFillItems)
-        } else {
-            let now = Instant::now();
+    pub(crate) fn shared(&self) -> &Arc<Shared> {
+        &self.shared
+    }
 
-            Ok(Self {
-                pos: 0,
-                total,
-                percent: if total.is_none() { None } else { Some(0.0) },
-                pre_inc,
-                thousands_separator,
-                message: Cow::Borrowed(""),
+    pub(crate) fn suspend<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let is_cleared = self.clear_line();
+        let result = f();
+        if is_cleared {
+            self.sync(Instant::now());
+            self.draw();
+        }
+        result
+    }
 
-                start_time: now,
-                speed: None,
-                eta_instant: None,
+    // Updates this `State` from changes made via `Shared` and from children,
+    // queueing a draw if anything has changed.
+    //
+    // Returns `true` if a draw was queued, i.e. drawer should be woken.
+    pub(crate) fn sync(&mut self, now: Instant) -> bool {
+        if self.is_finished {
+            return false;
+        }
 
-                items,
+        let mut is_changed = false;
 
-                prev_draw: None,
-                next_draw: Some(now + DEFAULT_DRAW_DELAY),
-                is_finished: false,
-            })
+        if self.shared.is_changed.swap(false, Ordering::SeqCst) {
+            self.pos = self.shared.pos.load(Ordering::SeqCst);
+            self.jumped += self.shared.jumped.swap(0, Ordering::SeqCst) as i64 as f64;
+            if let Some(message) = self.shared.message.pop() {
+                self.message = message;
+            }
+            is_changed = true;
+        }
+
+        // Children of dropped `Progress` are removed, keeping their contribution.
+        let mut child_pos = self.child_pos;
+        self.children.retain_mut(|child| {
+            let Some(state) = child.state.upgrade() else {
+                return false;
+            };
+            let mut state = state.lock();
+            state.sync(now);
+            let pos = child.weight as f64 * state.fraction();
+            if pos != child.pos {
+                child_pos += pos - child.pos;
+                child.pos = pos;
+                is_changed = true;
+            }
+            true
+        });
+        self.child_pos = child_pos;
+
+        if is_changed {
+            self.update(now);
+            self.queue_draw(now)
+        } else {
+            false
         }
     }
+}
 
-    // Returns
-    // - `OK(())` - was drawn
-    // - `Err(None)` - not drawn, no draw scheduled
-    // - `Err(Some(..))` - not drawn, draw is scheduled after returned duration
-    pub(crate) fn try_draw(&mut self) -> Result<(), Option<Duration>> {
+// ======================================================================
+// State - IMPL DRAW
+
+impl Draw for State {
+    fn is_finished(&self) -> bool {
+        self.is_finished
+    }
+
+    fn try_draw(&mut self) -> Result<(), Option<Duration>> {
         assert!(!self.is_finished);
 
+        // While a draw is queued, changes are synced only when it's due,
+        // so that they don't wake the drawer in the meantime.
+        let now = Instant::now();
+        if self.next_draw.map_or(true, |next_draw| next_draw <= now) {
+            self.sync(now);
+        }
+
         if let Some(next_draw) = self.next_draw {
-            let now = Instant::now();
             if next_draw > now {
                 Err(Some(next_draw - now))
             } else {
                 self.draw();
                 self.prev_draw = Some(now);
-                self.next_draw = None;
+                self.next_draw = self.is_animated().then(|| now + self.draw_interval());
                 Ok(())
             }
         } else {
@@ -323,66 +569,373 @@ impl State {
 // State - PRIVATE
 
 impl State {
-    fn draw(&mut self) {
-        if let Some((Width(width), _)) = terminal_size::terminal_size() {
-            let width = width as usize;
-
-            let mut pre_fill = String::with_capacity(width);
-            let mut fill = None;
-            let mut post_fill = String::with_capacity(width);
-
-            for item in &self.items {
-                let active = if fill.is_none() {
-                    &mut pre_fill
-                } else {
-                    &mut post_fill
-                };
-
-                match item {
-                    Item::Fill(item) => fill = Some(item),
-                    Item::Fn(f) => active.push_str(&f(self)),
-                    Item::Literal(s) => active.push_str(s),
-                }
+    // Returns bar of given width with given style or default style.
+    //
+    // Bar with unknown total is animated only if `is_tty`.
+    fn bar_text(&self, style: Option<&BarStyle>, width: usize, is_tty: bool) -> String {
+        let style = style.unwrap_or(&self.bar_style);
+        let colors = (is_tty && self.is_styled).then_some(&self.bar_colors);
+        if let Some(percent) = self.percent {
+            style.draw(width, percent / 100.0, colors)
+        } else if is_tty {
+            style.draw_bouncing(width, self.ticks(), colors)
+        } else {
+            " ".repeat(width)
+        }
+    }
+
+    // Clears drawn line, returning `true` if line was cleared.
+    fn clear_line(&mut self) -> bool {
+        if !self.is_finished {
+            if let Some(width) = self.target.as_ref().and_then(|target| target.width()) {
+                self.write_str(&format!("\r{:width$}\r", ""));
+                return true;
             }
+        }
+        false
+    }
 
-            let fill_width =
-                width.saturating_sub(pre_fill.chars().count() + post_fill.chars().count());
+    // Returns number of completed steps, including steps completed by children.
+    fn completed(&self) -> f64 {
+        let completed = if self.pre_inc {
+            self.pos.saturating_sub(1)
+        } else {
+            self.pos
+        };
+        completed as f64 + self.child_pos
+    }
+
+    fn draw(&mut self) {
+        let Some(target) = &self.target else {
+            return;
+        };
 
-            let mut line = String::with_capacity(width);
-            line.push_str(&pre_fill);
-            match fill {
-                Some(&FillItem::Bar) => {
+        if let Some(width) = target.width() {
+            let line = text::fit(&self.line(width, true), width);
+            self.write_str(&format!("\r{}", line));
+        } else {
+            match self.non_tty {
+                NonTtyMode::Hidden => (),
+                NonTtyMode::Interval(_) => self.draw_non_tty(),
+                NonTtyMode::PercentStep(step) => {
                     if let Some(percent) = self.percent {
-                        let done_width =
-                            ((fill_width as f64 * percent / 100.0) as usize).min(fill_width);
-                        line.push_str(&"#".repeat(done_width));
-                        line.push_str(&"-".repeat(fill_width - done_width));
-                    } else {
-                        line.push_str(&" ".repeat(fill_width));
+                        let step = (percent / step) as u64;
+                        if self.non_tty_step.map_or(true, |prev| step > prev) {
+                            self.non_tty_step = Some(step);
+                            self.draw_non_tty();
+                        }
                     }
                 }
+            }
+        }
+    }
 
-                Some(FillItem::Message) => {
-                    line.push_str(&format!("{:fill_width$.fill_width$}", self.message))
-                }
+    // Draws once more with `"\n"` after `Progress` has been finished.
+    fn draw_final(&mut self) {
+        let Some(target) = &self.target else {
+            return;
+        };
+
+        if let Some(width) = target.width() {
+            let line = text::fit(&self.line(width, true), width);
+            self.write_str(&format!("\r{}\n", line));
+        } else if self.non_tty != NonTtyMode::Hidden {
+            self.draw_non_tty();
+        }
+    }
+
+    fn draw_interval(&self) -> Duration {
+        match (&self.target, self.non_tty) {
+            (Some(target), NonTtyMode::Interval(interval)) if target.width().is_none() => {
+                interval.max(DEFAULT_DRAW_INTERVAL)
+            }
+            _ => DEFAULT_DRAW_INTERVAL,
+        }
+    }
+
+    // Prints line unless it's same as previous one.
+    fn draw_non_tty(&mut self) {
+        let mut line = self.line(NON_TTY_WIDTH, false);
+        line.truncate(line.trim_end().len());
+        if line != self.non_tty_line {
+            self.write_str(&format!("{}\n", line));
+            self.non_tty_line = line;
+        }
+    }
+
+    // Returns text of given fill item with given width.
+    fn fill_text(&self, item: &Item, fill_width: usize, is_tty: bool) -> String {
+        let is_styled = is_tty && self.is_styled;
+        match item {
+            Item::Fill(FillItem::Bar(style)) => self.bar_text(style.as_ref(), fill_width, is_tty),
+
+            Item::Fill(FillItem::Message(mode)) => text::fit(
+                &text::truncate_with(&self.message, fill_width, *mode),
+                fill_width,
+            ),
+
+            Item::Styled(style, item) if is_styled => {
+                style.paint(&self.fill_text(item, fill_width, is_tty))
+            }
+
+            Item::Styled(_, item) | Item::Weighted(_, item) => {
+                self.fill_text(item, fill_width, is_tty)
+            }
+
+            _ => String::new(),
+        }
+    }
+
+    // Returns completed fraction between 0 and 1.
+    fn fraction(&self) -> f64 {
+        self.percent
+            .map_or(0.0, |percent| (percent / 100.0).min(1.0))
+    }
+
+    // Returns `true` if line needs to be redrawn periodically.
+    fn is_animated(&self) -> bool {
+        let is_tty = self
+            .target
+            .as_ref()
+            .map_or(true, |target| target.width().is_some());
+        is_tty
+            && !self.is_finished
+            && self.paused_at.is_none()
+            && self
+                .items
+                .iter()
+                .any(|item| item.is_animated(self.total.is_some()))
+    }
+
+    // Returns text of given non-fill item.
+    //
+    // Spinner is animated only if `is_tty`.
+    fn item_text(&self, item: &Item, is_tty: bool) -> String {
+        match item {
+            Item::Bar(width, style) => self.bar_text(style.as_ref(), *width, is_tty),
+            Item::Fill(_) => String::new(),
+            Item::Fn(f) => f(self),
+            Item::Literal(s) => s.clone(),
+
+            Item::Spinner([]) => String::new(),
+            Item::Spinner(frames) => {
+                let tick = if is_tty { self.ticks() } else { 0 };
+                frames[tick % frames.len()].to_owned()
+            }
 
-                None => (),
+            Item::Styled(style, item) if is_tty && self.is_styled => {
+                style.paint(&self.item_text(item, is_tty))
             }
-            line.push_str(&post_fill);
 
-            eprint!("\r{:width$.width$}", line);
+            Item::Priority(_, items) => items
+                .iter()
+                .map(|item| self.item_text(item, is_tty))
+                .collect(),
+
+            Item::Styled(_, item) | Item::Weighted(_, item) => self.item_text(item, is_tty),
         }
     }
 
-    fn queue_draw(&mut self, now: Instant, drawer: &JoinHandle<()>) {
+    // Returns line of given width, with styles if `is_tty` and styles are enabled.
+    //
+    // If line doesn't fit, items with priority are dropped, lowest priority first,
+    // until line fits with fill item at least `min_fill_width` wide.
+    fn line(&self, width: usize, is_tty: bool) -> String {
+        // Text and width of each non-fill item, empty for fill item.
+        let mut texts = Vec::with_capacity(self.items.len());
+        let mut priorities = Vec::with_capacity(self.items.len());
+        for item in &self.items {
+            if item.is_fill() {
+                texts.push((String::new(), 0));
+                priorities.push(None);
+            } else {
+                let text = self.item_text(item, is_tty);
+                let text_width = text::width(&text);
+                texts.push((text, text_width));
+                priorities.push(item.priority());
+            }
+        }
+
+        let fills = self
+            .items
+            .iter()
+            .filter(|item| item.is_fill())
+            .map(Item::fill_width)
+            .collect::<Vec<_>>();
+        let min_fill_width = if fills.is_empty() {
+            0
+        } else {
+            fills
+                .iter()
+                .map(FillWidth::min)
+                .sum::<usize>()
+                .max(self.min_fill_width)
+        };
+        let mut items_width: usize = texts.iter().map(|(_, width)| width).sum();
+
+        // Of items with same priority, the last one is dropped first.
+        while items_width + min_fill_width > width {
+            let Some((index, _)) = priorities
+                .iter()
+                .enumerate()
+                .filter_map(|(index, priority)| Some((index, (*priority)?)))
+                .min_by_key(|&(index, priority)| (priority, Reverse(index)))
+            else {
+                break;
+            };
+            priorities[index] = None;
+            items_width -= texts[index].1;
+            texts[index] = (String::new(), 0);
+        }
+
+        let mut fill_widths =
+            internal::split_fill_width(&fills, width.saturating_sub(items_width)).into_iter();
+
+        let mut line = String::with_capacity(width);
+        for (item, (text, _)) in self.items.iter().zip(texts) {
+            if item.is_fill() {
+                let fill_width = fill_widths.next().unwrap_or(0);
+                line.push_str(&self.fill_text(item, fill_width, is_tty));
+            } else {
+                line.push_str(&text);
+            }
+        }
+        line
+    }
+
+    // Queues a draw unless one is already queued, returning `true` if queued.
+    fn queue_draw(&mut self, now: Instant) -> bool {
         if !self.is_finished && self.next_draw.is_none() {
             let mut next_draw = now + DEFAULT_DRAW_DELAY;
             if let Some(prev_draw) = self.prev_draw {
-                next_draw = next_draw.max(prev_draw + DEFAULT_DRAW_INTERVAL);
+                next_draw = next_draw.max(prev_draw + self.draw_interval());
             }
             self.next_draw = Some(next_draw);
+            true
+        } else {
+            false
+        }
+    }
 
-            drawer.thread().unpark();
+    // Returns number of draw intervals elapsed, used as frame of animations.
+    fn ticks(&self) -> usize {
+        (self.elapsed().as_nanos() / DEFAULT_DRAW_INTERVAL.as_nanos()) as usize
+    }
+
+    // Updates speed, percent and ETA, giving estimator a sample if position has changed.
+    fn update(&mut self, now: Instant) {
+        let completed = self.completed();
+        let sample = completed - self.jumped;
+        if sample != self.latest_sample.1 {
+            self.estimator.add(self.unpaused_time(now), sample);
+            self.latest_sample = (now, sample);
+            self.speed = self.estimator.speed();
         }
+
+        // Zero total is complete, instead of dividing by zero.
+        self.percent = self.total.map(|total| match total {
+            0 => 100.0,
+            total => completed / total as f64 * 100.0,
+        });
+
+        self.eta_instant = match self.total {
+            Some(total) if completed <= total as f64 => self
+                .estimator
+                .remaining((total as f64 - self.jumped).max(0.0) as u64)
+                .and_then(|remaining| self.latest_sample.0.checked_add(remaining)),
+            _ => None,
+        };
     }
+
+    // Returns given time shifted back by time spent paused,
+    // i.e. as if `Progress` had never been paused.
+    fn unpaused_time(&self, now: Instant) -> Instant {
+        let paused = match self.paused_at {
+            Some(paused_at) => self.paused + (now - paused_at),
+            None => self.paused,
+        };
+        now.checked_sub(paused).unwrap_or(now)
+    }
+
+    fn write_str(&mut self, s: &str) {
+        if let Some(target) = &mut self.target {
+            target.write_str(s);
+        }
+    }
+}
+
+// ======================================================================
+// Shared - CRATE
+
+// Part of `State` which is updated by `Progress` without locking `State`.
+//
+// Changes are applied to `State` by `State::sync`.
+pub(crate) struct Shared {
+    pos: AtomicU64,
+    // Net jump of `pos` by `set_pos` and `dec` after latest sync, as wrapping `i64`.
+    jumped: AtomicU64,
+    // Message set after latest sync, newer message replacing older one.
+    message: ArrayQueue<Cow<'static, str>>,
+    // This is `true` if `pos` or `message` has changed after latest sync.
+    is_changed: AtomicBool,
+}
+
+// Methods return `true` if drawer should be woken,
+// i.e. if this is the first change after latest sync.
+impl Shared {
+    pub(crate) fn dec(&self, steps: u64) -> bool {
+        let prev = self
+            .pos
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pos| {
+                Some(pos.saturating_sub(steps))
+            })
+            .unwrap_or_else(|pos| pos);
+        self.add_jump(prev.saturating_sub(steps), prev)
+    }
+
+    pub(crate) fn inc(&self, steps: u64) -> bool {
+        self.pos.fetch_add(steps, Ordering::SeqCst);
+        self.set_changed()
+    }
+
+    pub(crate) fn message(&self, message: Cow<'static, str>) -> bool {
+        self.message.force_push(message);
+        self.set_changed()
+    }
+
+    pub(crate) fn set_pos(&self, pos: u64) -> bool {
+        let prev = self.pos.swap(pos, Ordering::SeqCst);
+        self.add_jump(pos, prev)
+    }
+}
+
+// ======================================================================
+// Shared - PRIVATE
+
+impl Shared {
+    // Records jump of position from `prev` to `pos`.
+    fn add_jump(&self, pos: u64, prev: u64) -> bool {
+        self.jumped
+            .fetch_add(pos.wrapping_sub(prev), Ordering::SeqCst);
+        self.set_changed()
+    }
+
+    // `SeqCst` is needed so that `State::sync` can't miss a change
+    // which doesn't wake the drawer. Plain load first avoids writing
+    // to shared cache line on every change.
+    fn set_changed(&self) -> bool {
+        !self.is_changed.load(Ordering::SeqCst) && !self.is_changed.swap(true, Ordering::SeqCst)
+    }
+}
+
+// ======================================================================
+// Child - PRIVATE
+
+struct Child {
+    // This can't be upgraded after all clones of child `Progress` have been dropped.
+    state: Weak<Mutex<State>>,
+    // Number of steps of parent which this child represents.
+    weight: u64,
+    // Position currently contributed to parent.
+    pos: f64,
 }
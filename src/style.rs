@@ -0,0 +1,229 @@
+// ======================================================================
+// CONST - CRATE
+
+// Resets all styles.
+pub(crate) const RESET: &str = "\x1b[0m";
+
+// ======================================================================
+// Color - PUBLIC
+
+/// Terminal color used with [`Style`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    /// Black.
+    Black,
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Magenta.
+    Magenta,
+    /// Cyan.
+    Cyan,
+    /// White.
+    White,
+    /// Color of 256-color palette.
+    Fixed(u8),
+}
+
+// ======================================================================
+// Style - PUBLIC
+
+/// Text style, i.e. colors and attributes, shown with ANSI escape codes.
+///
+/// Styles are used with [style items] and [`ProgressBuilder::bar_colors`].
+///
+/// Styles are shown only if target of [`Progress`] is a terminal
+/// and `NO_COLOR` environment variable is not set.
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::{progress_builder, Color, Style};
+///
+/// let progress = progress_builder!(
+///     (green bar_fill) " " (style (Style::new().fg(Color::Fixed(208)).bold()) eta)
+/// )
+/// .build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// [style items]: crate#styles
+/// [`Progress`]: crate::Progress
+/// [`ProgressBuilder::bar_colors`]: crate::ProgressBuilder::bar_colors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// Returns this style with given background color.
+    pub const fn bg(self, color: Color) -> Self {
+        Self {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    /// Returns this style with bold text.
+    pub const fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    /// Returns this style with dim text.
+    pub const fn dim(self) -> Self {
+        Self { dim: true, ..self }
+    }
+
+    /// Returns this style with given foreground color.
+    pub const fn fg(self, color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    /// Returns this style with italic text.
+    pub const fn italic(self) -> Self {
+        Self {
+            italic: true,
+            ..self
+        }
+    }
+
+    /// Creates empty style, i.e. text is shown as-is.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    /// Returns this style with underlined text.
+    pub const fn underline(self) -> Self {
+        Self {
+            underline: true,
+            ..self
+        }
+    }
+}
+
+// ======================================================================
+// Style - CRATE
+
+impl Style {
+    // Returns given text with this style applied.
+    //
+    // Any style reset within given text is followed by this style again,
+    // so that styles can be nested.
+    pub(crate) fn paint(&self, text: &str) -> String {
+        let codes = self.codes();
+        if codes.is_empty() || text.is_empty() {
+            return text.to_owned();
+        }
+
+        let start = format!("\x1b[{}m", codes.join(";"));
+        format!(
+            "{}{}{}",
+            start,
+            text.replace(RESET, &format!("{}{}", RESET, start)),
+            RESET
+        )
+    }
+}
+
+// ======================================================================
+// Style - PRIVATE
+
+impl Style {
+    fn codes(&self) -> Vec<String> {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_owned());
+        }
+        if self.dim {
+            codes.push("2".to_owned());
+        }
+        if self.italic {
+            codes.push("3".to_owned());
+        }
+        if self.underline {
+            codes.push("4".to_owned());
+        }
+        if let Some(color) = self.fg {
+            codes.push(color_code(color, 30));
+        }
+        if let Some(color) = self.bg {
+            codes.push(color_code(color, 40));
+        }
+        codes
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+// Returns SGR code of given color, with `base` 30 for foreground and 40 for background.
+fn color_code(color: Color, base: u8) -> String {
+    let offset = match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::White => 7,
+        Color::Fixed(n) => return format!("{};5;{}", base + 8, n),
+    };
+    (base + offset).to_string()
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paint_empty_style() {
+        assert_eq!(Style::new().paint("foo"), "foo");
+    }
+
+    #[test]
+    fn paint_codes() {
+        assert_eq!(
+            Style::new().fg(Color::Red).bold().paint("foo"),
+            "\x1b[1;31mfoo\x1b[0m"
+        );
+        assert_eq!(
+            Style::new().bg(Color::Fixed(208)).paint("foo"),
+            "\x1b[48;5;208mfoo\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn paint_nested() {
+        let inner = Style::new().bold().paint("foo");
+        assert_eq!(
+            Style::new()
+                .fg(Color::Green)
+                .paint(&format!("{}bar", inner)),
+            "\x1b[32m\x1b[1mfoo\x1b[0m\x1b[32mbar\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use terminal_size::Width;
+
+// ======================================================================
+// NonTtyMode - PUBLIC
+
+/// How [`Progress`] is shown when [`ProgressTarget`] is not a terminal.
+///
+/// Mode is set with [`ProgressBuilder::non_tty`], default is [`Hidden`].
+///
+/// In modes other than [`Hidden`] the line is printed without `"\r"`,
+/// terminated with `"\n"`, with trailing whitespace removed
+/// and with fill items filling a line of 80 characters.
+/// Line is not printed if it's same as previously printed line.
+/// Final line is always printed when `Progress` is finished,
+/// except with [`finish_and_clear`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use ml_progress::{progress_builder, NonTtyMode};
+///
+/// let progress = progress_builder!("[" percent "] " pos "/" total)
+///     .total(Some(10))
+///     .non_tty(NonTtyMode::Interval(Duration::from_secs(10)))
+///     .build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// ```text
+/// [  0%] 0/10
+/// [ 40%] 4/10
+/// [ 90%] 9/10
+/// [100%] 10/10
+/// ```
+///
+/// [`Hidden`]: NonTtyMode::Hidden
+/// [`Progress`]: crate::Progress
+/// [`ProgressBuilder::non_tty`]: crate::ProgressBuilder::non_tty
+/// [`finish_and_clear`]: crate::Progress::finish_and_clear
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonTtyMode {
+    /// Nothing is shown.
+    Hidden,
+
+    /// Line is printed at most once per given interval.
+    Interval(Duration),
+
+    /// Line is printed whenever percentual completion
+    /// reaches next multiple of given percent.
+    ///
+    /// Nothing is printed before finish if `total` is `None`.
+    ///
+    /// Percent must be positive and finite,
+    /// otherwise building `Progress` fails with [`Error::InvalidPercentStep`].
+    ///
+    /// [`Error::InvalidPercentStep`]: crate::Error::InvalidPercentStep
+    PercentStep(f64),
+}
+
+// ======================================================================
+// ProgressTarget - PUBLIC
+
+/// Where [`Progress`] is drawn.
+///
+/// Target is set with [`ProgressBuilder::target`], default is [`Stderr`].
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::{progress_builder, ProgressTarget};
+///
+/// let progress = progress_builder!()
+///     .target(ProgressTarget::Stdout)
+///     .build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// [`Progress`]: crate::Progress
+/// [`ProgressBuilder::target`]: crate::ProgressBuilder::target
+/// [`Stderr`]: ProgressTarget::Stderr
+pub enum ProgressTarget {
+    /// Draws to `STDERR` if it's a terminal.
+    Stderr,
+
+    /// Draws to `STDOUT` if it's a terminal.
+    Stdout,
+
+    /// Draws to custom writer.
+    Writer {
+        /// Writer to draw to.
+        writer: Box<dyn Write + Send>,
+        /// Width of the line, or `None` if writer is not a terminal.
+        ///
+        /// If this is `None` then output follows [`NonTtyMode`],
+        /// i.e. nothing is drawn by default.
+        width: Option<usize>,
+    },
+}
+
+impl ProgressTarget {
+    /// Creates [`Writer`] target from given writer and line width.
+    ///
+    /// If `width` is `None` then writer is not considered to be a terminal
+    /// and output follows [`NonTtyMode`], i.e. nothing is drawn by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ml_progress::{progress_builder, ProgressTarget};
+    ///
+    /// let progress = progress_builder!()
+    ///     .target(ProgressTarget::writer(std::io::sink(), Some(80)))
+    ///     .build()?;
+    /// # Ok::<(), ml_progress::Error>(())
+    /// ```
+    ///
+    /// [`Writer`]: ProgressTarget::Writer
+    pub fn writer(writer: impl Write + Send + 'static, width: Option<usize>) -> Self {
+        Self::Writer {
+            writer: Box::new(writer),
+            width,
+        }
+    }
+}
+
+// ======================================================================
+// ProgressTarget - CRATE
+
+impl ProgressTarget {
+    // Returns width of the terminal or `None` if target is not a terminal.
+    pub(crate) fn width(&self) -> Option<usize> {
+        match self {
+            ProgressTarget::Stderr => stderr_width(),
+            ProgressTarget::Stdout => stdout_width(),
+            ProgressTarget::Writer { width, .. } => *width,
+        }
+    }
+
+    // Writes given string and flushes, ignoring any errors.
+    pub(crate) fn write_str(&mut self, s: &str) {
+        let _ = match self {
+            ProgressTarget::Stderr => write_and_flush(&mut io::stderr().lock(), s),
+            ProgressTarget::Stdout => write_and_flush(&mut io::stdout().lock(), s),
+            ProgressTarget::Writer { writer, .. } => write_and_flush(writer, s),
+        };
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+#[cfg(unix)]
+fn stderr_width() -> Option<usize> {
+    use std::os::unix::io::AsRawFd;
+    terminal_size::terminal_size_using_fd(io::stderr().as_raw_fd()).map(|(Width(w), _)| w as usize)
+}
+
+#[cfg(unix)]
+fn stdout_width() -> Option<usize> {
+    use std::os::unix::io::AsRawFd;
+    terminal_size::terminal_size_using_fd(io::stdout().as_raw_fd()).map(|(Width(w), _)| w as usize)
+}
+
+#[cfg(windows)]
+fn stderr_width() -> Option<usize> {
+    use std::os::windows::io::AsRawHandle;
+    terminal_size::terminal_size_using_handle(io::stderr().as_raw_handle())
+        .map(|(Width(w), _)| w as usize)
+}
+
+#[cfg(windows)]
+fn stdout_width() -> Option<usize> {
+    use std::os::windows::io::AsRawHandle;
+    terminal_size::terminal_size_using_handle(io::stdout().as_raw_handle())
+        .map(|(Width(w), _)| w as usize)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn stderr_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(Width(w), _)| w as usize)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn stdout_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(Width(w), _)| w as usize)
+}
+
+fn write_and_flush(writer: &mut impl Write, s: &str) -> io::Result<()> {
+    writer.write_all(s.as_bytes())?;
+    writer.flush()
+}
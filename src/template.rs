@@ -0,0 +1,190 @@
+use crate::{internal::Item, item, Error};
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Parses items from template string at runtime.
+///
+/// This is the runtime alternative to items given to [`progress!`]
+/// and [`progress_builder!`] macros, e.g. for reading items from
+/// a configuration file. Returned items are given to [`ProgressBuilder::new`].
+///
+/// - Items are given in braces, e.g. `{bar_fill}`.
+/// - Any other text is shown as-is, with `{{` and `}}` used for `{` and `}`.
+/// - All single-token [items] are supported, e.g. `eta_hms` and `pos_group`.
+///   Items with parentheses, e.g. `(eta FORMAT)`, are not supported.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidTemplate`] if template contains unknown item,
+/// unclosed `{` or unescaped `}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ml_progress::{parse_items, ProgressBuilder};
+///
+/// let items = parse_items("{bar_fill} {pos_group}/{total_group} ({eta_hms})")?;
+/// let progress = ProgressBuilder::new(items).total(Some(10)).build()?;
+/// # Ok::<(), ml_progress::Error>(())
+/// ```
+///
+/// ```rust
+/// use ml_progress::{parse_items, Error};
+///
+/// assert_eq!(
+///     parse_items("{pos}/{totl}").err(),
+///     Some(Error::InvalidTemplate {
+///         token: "{totl}".to_owned(),
+///         pos: 6
+///     })
+/// );
+/// ```
+///
+/// [items]: crate#items
+/// [`ProgressBuilder::new`]: crate::ProgressBuilder::new
+/// [`progress!`]: crate::progress
+/// [`progress_builder!`]: crate::progress_builder
+pub fn parse_items(template: &str) -> Result<Vec<Item>, Error> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '{' if chars.next_if(|&(_, c)| c == '{').is_some() => literal.push('{'),
+            '}' if chars.next_if(|&(_, c)| c == '}').is_some() => literal.push('}'),
+
+            '{' => {
+                let rest = &template[pos..];
+                let Some(len) = rest.find('}').map(|end| end + 1) else {
+                    return Err(invalid(rest, pos));
+                };
+                let token = &rest[..len];
+                let Some(item) = parse_item(&token[1..len - 1]) else {
+                    return Err(invalid(token, pos));
+                };
+
+                if !literal.is_empty() {
+                    items.push(Item::Literal(std::mem::take(&mut literal)));
+                }
+                items.push(item);
+                while chars.next_if(|&(next, _)| next < pos + len).is_some() {}
+            }
+
+            '}' => return Err(invalid("}", pos)),
+
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        items.push(Item::Literal(literal));
+    }
+    Ok(items)
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+fn invalid(token: &str, pos: usize) -> Error {
+    Error::InvalidTemplate {
+        token: token.to_owned(),
+        pos,
+    }
+}
+
+fn parse_item(name: &str) -> Option<Item> {
+    Some(match name {
+        "bar_fill" => item!(bar_fill),
+        "elapsed" => item!(elapsed),
+        "elapsed_hms" => item!(elapsed_hms),
+        "eta" => item!(eta),
+        "eta_hms" => item!(eta_hms),
+        "message" => item!(message),
+        "message_fill" => item!(message_fill),
+        "paused" => item!(paused),
+        "percent" => item!(percent),
+        "pos" => item!(pos),
+        "pos_group" => item!(pos_group),
+        "pos_bin" => item!(pos_bin),
+        "pos_dec" => item!(pos_dec),
+        "speed" => item!(speed),
+        "speed_int" => item!(speed_int),
+        "speed_group" => item!(speed_group),
+        "speed_bin" => item!(speed_bin),
+        "speed_dec" => item!(speed_dec),
+        "spinner" => item!(spinner),
+        "total" => item!(total),
+        "total_group" => item!(total_group),
+        "total_bin" => item!(total_bin),
+        "total_dec" => item!(total_dec),
+        _ => return None,
+    })
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::FillItem;
+
+    fn parse(template: &str) -> Vec<String> {
+        parse_items(template)
+            .unwrap()
+            .into_iter()
+            .map(|item| match item {
+                Item::Bar(..) | Item::Fill(FillItem::Bar(_)) => "<bar>".to_owned(),
+                Item::Fill(FillItem::Message(_)) => "<message>".to_owned(),
+                Item::Fn(_) => "<fn>".to_owned(),
+                Item::Literal(s) => s,
+                Item::Priority(..) => "<priority>".to_owned(),
+                Item::Spinner(_) => "<spinner>".to_owned(),
+                Item::Styled(..) => "<styled>".to_owned(),
+                Item::Weighted(..) => "<weighted>".to_owned(),
+            })
+            .collect()
+    }
+
+    fn parse_err(template: &str) -> (String, usize) {
+        match parse_items(template) {
+            Err(Error::InvalidTemplate { token, pos }) => (token, pos),
+            _ => panic!("expected `Error::InvalidTemplate`"),
+        }
+    }
+
+    #[test]
+    fn items_and_literals() {
+        assert_eq!(
+            parse("[{percent}] {pos}/{total} {bar_fill}"),
+            ["[", "<fn>", "] ", "<fn>", "/", "<fn>", " ", "<bar>"]
+        );
+        assert_eq!(parse("{message_fill}"), ["<message>"]);
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(parse("{{{pos}}}"), ["{", "<fn>", "}"]);
+        assert_eq!(parse("ä{{}}ö"), ["ä{}ö"]);
+    }
+
+    #[test]
+    fn unknown_item() {
+        assert_eq!(parse_err("ä {foo} {pos}"), ("{foo}".to_owned(), 3));
+        assert_eq!(parse_err("{}"), ("{}".to_owned(), 0));
+        assert_eq!(parse_err("{ pos }"), ("{ pos }".to_owned(), 0));
+    }
+
+    #[test]
+    fn unclosed_brace() {
+        assert_eq!(parse_err("{pos} {total"), ("{total".to_owned(), 6));
+    }
+
+    #[test]
+    fn unescaped_closing_brace() {
+        assert_eq!(parse_err("{pos} }"), ("}".to_owned(), 6));
+    }
+}
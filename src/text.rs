@@ -0,0 +1,245 @@
+// Width handling of drawn text, which can contain ANSI escape sequences.
+//
+// Width is display width of grapheme clusters, so e.g. CJK characters
+// and most emoji are two columns wide and combining characters are zero wide.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::style::RESET;
+
+// ======================================================================
+// CONST - PRIVATE
+
+const ELLIPSIS: &str = "…";
+
+// ======================================================================
+// Truncate - PUBLIC
+
+/// How text is truncated when it doesn't fit, used with `message` items.
+///
+/// Examples below are `/home/user/project/src/file.rs`
+/// truncated to width 15 (`End` and `Start`) or 20 (`Middle` and `Path`).
+///
+/// See [`message_fill`] for an example.
+///
+/// [`message_fill`]: crate#message_fill
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Truncate {
+    /// Cuts the end, e.g. `/home/user/proj`.
+    End,
+    /// Cuts the start, e.g. `ect/src/file.rs`.
+    Start,
+    /// Replaces the middle with ellipsis, e.g. `/home/user…c/file.rs`.
+    Middle,
+    /// Replaces the middle with ellipsis, keeping the file name after last `/` or `\\`,
+    /// e.g. `/home/user/…/file.rs`.
+    ///
+    /// If file name doesn't fit, cuts the start like [`Truncate::Start`].
+    Path,
+}
+
+// ======================================================================
+// FUNCTIONS - CRATE
+
+// Pads with spaces or truncates given text to exactly given width.
+pub(crate) fn fit(text: &str, width: usize) -> String {
+    let mut text = truncate(text, width);
+    let text_width = self::width(&text);
+    text.push_str(&" ".repeat(width - text_width));
+    text
+}
+
+// Truncates given text to at most given width with given mode.
+//
+// Unlike `truncate`, escape sequences aren't handled specially.
+pub(crate) fn truncate_with(text: &str, width: usize, mode: Truncate) -> String {
+    if self::width(text) <= width {
+        return text.to_owned();
+    }
+
+    match mode {
+        Truncate::End => truncate(text, width),
+        Truncate::Start => truncate_start(text, width),
+        Truncate::Middle if width == 0 => String::new(),
+        Truncate::Middle => {
+            let available = width - 1;
+            let start = truncate(text, available - available / 2);
+            let end = truncate_start(text, available - self::width(&start));
+            format!("{}{}{}", start, ELLIPSIS, end)
+        }
+        Truncate::Path => {
+            let file_name = text.rfind(['/', '\\']).map_or(text, |index| &text[index..]);
+            let file_name_width = self::width(file_name);
+            if file_name_width < width {
+                let start = truncate(text, width - 1 - file_name_width);
+                format!("{}{}{}", start, ELLIPSIS, file_name)
+            } else {
+                truncate_with(file_name, width, Truncate::Start)
+            }
+        }
+    }
+}
+
+// Truncates given text to at most given width,
+// resetting styles if anything styled was cut off.
+//
+// Grapheme clusters are never split, so result can be narrower than
+// given width if a wide character doesn't fit.
+pub(crate) fn truncate(text: &str, width: usize) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut result_width = 0;
+    let mut has_escape = false;
+
+    for part in parts(text) {
+        match part {
+            Part::Escape(escape) => {
+                result.push_str(escape);
+                has_escape = true;
+            }
+            Part::Grapheme(grapheme, grapheme_width) if result_width + grapheme_width <= width => {
+                result.push_str(grapheme);
+                result_width += grapheme_width;
+            }
+            Part::Grapheme(..) => {
+                if has_escape {
+                    result.push_str(RESET);
+                }
+                break;
+            }
+        }
+    }
+    result
+}
+
+// Returns display width of given text, ignoring escape sequences.
+pub(crate) fn width(text: &str) -> usize {
+    parts(text)
+        .iter()
+        .map(|part| match part {
+            Part::Escape(_) => 0,
+            Part::Grapheme(_, width) => *width,
+        })
+        .sum()
+}
+
+// ======================================================================
+// Part - PRIVATE
+
+enum Part<'a> {
+    Escape(&'a str),
+    // Grapheme cluster and its display width.
+    Grapheme(&'a str, usize),
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+// Returns length of escape sequence at start of given text, if any.
+fn escape_len(text: &str) -> Option<usize> {
+    let params = text.strip_prefix("\x1b[")?;
+    params
+        .bytes()
+        .position(|b| (0x40..=0x7e).contains(&b))
+        .map(|index| index + 3)
+}
+
+// Truncates start of given text to at most given width.
+fn truncate_start(text: &str, width: usize) -> String {
+    let mut start = text.len();
+    let mut result_width = 0;
+    for (index, grapheme) in text.grapheme_indices(true).rev() {
+        result_width += grapheme.width();
+        if result_width > width {
+            break;
+        }
+        start = index;
+    }
+    text[start..].to_owned()
+}
+
+// Splits given text into escape sequences and grapheme clusters.
+fn parts(text: &str) -> Vec<Part<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(len) = escape_len(rest) {
+            parts.push(Part::Escape(&rest[..len]));
+            rest = &rest[len..];
+        } else {
+            // Escape can't start at first character here, so search after it.
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let len = rest[first..]
+                .find("\x1b[")
+                .map_or(rest.len(), |index| index + first);
+            parts.extend(
+                rest[..len]
+                    .graphemes(true)
+                    .map(|grapheme| Part::Grapheme(grapheme, grapheme.width())),
+            );
+            rest = &rest[len..];
+        }
+    }
+    parts
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_ignores_escapes() {
+        assert_eq!(width("\x1b[1;32mfoo\x1b[0mä"), 4);
+        assert_eq!(width(""), 0);
+    }
+
+    #[test]
+    fn width_of_wide_and_combining() {
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(width("a👍b"), 4);
+        assert_eq!(width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn fit_pads() {
+        assert_eq!(fit("\x1b[1mab\x1b[0m", 4), "\x1b[1mab\x1b[0m  ");
+        assert_eq!(fit("日本語", 5), "日本 ");
+    }
+
+    #[test]
+    fn truncate_keeps_escapes_and_resets() {
+        assert_eq!(truncate("\x1b[1mabc\x1b[0md", 2), "\x1b[1mab\x1b[0m");
+        assert_eq!(truncate("\x1b[1mab\x1b[0m", 2), "\x1b[1mab\x1b[0m");
+        assert_eq!(truncate("abc", 2), "ab");
+    }
+
+    #[test]
+    fn truncate_with_modes() {
+        let path = "/home/user/project/src/file.rs";
+        assert_eq!(truncate_with(path, 40, Truncate::Middle), path);
+        assert_eq!(truncate_with(path, 15, Truncate::End), "/home/user/proj");
+        assert_eq!(truncate_with(path, 15, Truncate::Start), "ect/src/file.rs");
+        assert_eq!(
+            truncate_with(path, 20, Truncate::Middle),
+            "/home/user…c/file.rs"
+        );
+        assert_eq!(
+            truncate_with(path, 20, Truncate::Path),
+            "/home/user/…/file.rs"
+        );
+        assert_eq!(truncate_with(path, 6, Truncate::Path), "ile.rs");
+        assert_eq!(truncate_with("日本語日本語", 6, Truncate::Middle), "日…語");
+        assert_eq!(truncate_with(path, 0, Truncate::Middle), "");
+    }
+
+    #[test]
+    fn truncate_never_splits_graphemes() {
+        assert_eq!(truncate("a日本", 2), "a");
+        assert_eq!(truncate("ae\u{301}b", 2), "ae\u{301}");
+        assert_eq!(truncate("\x1b[1m", 0), "\x1b[1m");
+        assert_eq!(truncate("日\x1b[1m本", 2), "日\x1b[1m\x1b[0m");
+    }
+}